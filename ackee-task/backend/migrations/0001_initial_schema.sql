@@ -0,0 +1,36 @@
+-- Tables previously created ad hoc at startup, IF NOT EXISTS adopts databases that already have them
+CREATE TABLE IF NOT EXISTS libraries (
+    id UUID PRIMARY KEY,
+    timestamp TIMESTAMPTZ NOT NULL,
+    name TEXT NOT NULL,
+    library_address TEXT NOT NULL UNIQUE
+);
+
+CREATE TABLE IF NOT EXISTS nfts (
+    id UUID PRIMARY KEY,
+    timestamp TIMESTAMPTZ NOT NULL,
+    nft_price BIGINT NOT NULL,
+    nft_bid_step BIGINT NOT NULL,
+    name TEXT NOT NULL,
+    library_address TEXT NOT NULL,
+    nft_address TEXT NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS idx_nfts_library_address ON nfts(library_address);
+
+CREATE TABLE IF NOT EXISTS placed_bids (
+    id UUID PRIMARY KEY,
+    timestamp TIMESTAMPTZ NOT NULL,
+    nft_name TEXT NOT NULL,
+    nft_address TEXT NOT NULL,
+    bidder TEXT NOT NULL,
+    amount BIGINT NOT NULL
+);
+
+CREATE TABLE IF NOT EXISTS winners (
+    id UUID PRIMARY KEY,
+    timestamp TIMESTAMPTZ NOT NULL,
+    nft_name TEXT NOT NULL,
+    recipient TEXT NOT NULL,
+    owner TEXT NOT NULL
+);
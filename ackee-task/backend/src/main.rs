@@ -1,5 +1,6 @@
 use std::{ env };
 use actix_web::{ App, HttpServer, web };
+use clap::Parser;
 
 use crate::{
     routes::{
@@ -15,11 +16,14 @@ use crate::{
     },
     services::{
         grpc_service::transactions_subscribe,
-        library_service::{ create_init_library_event, create_library_table_if_not_exists },
-        nft_service::{ create_mint_nft_event, create_nft_table_if_not_exists },
-        placed_bids_service::{ create_bid_placed_event, create_bid_table_if_not_exists },
-        winners_service::{ create_transfer_nft_event, create_winners_table_if_not_exists },
+        db_service::Database,
+        library_service::create_init_library_event,
+        migration_service::run_migrations,
+        nft_service::create_mint_nft_event,
+        placed_bids_service::create_bid_placed_event,
+        winners_service::create_transfer_nft_event,
     },
+    structs::{ app_state_struct::AppState, config_struct::{ Command, Config } },
 };
 
 const PROGRAM_ADDRESS: &str = "EDFwnAysttkv5TW7davfHDuFctxnZxNRb8WCU2AVf7um";
@@ -38,24 +42,42 @@ async fn main() -> std::io::Result<()> {
 
     env_logger::init();
 
-    // Connect to database with proper error handling
-    let connection = services::db_service::Database::connect().await
+    let config = Config::parse();
+
+    if let Some(Command::Migrate) = config.command {
+        let database = Database::connect(&config).map_err(std::io::Error::other)?;
+
+        return run_migrations(&database).await.map_err(|e| {
+            log::error!("Failed to migrate database: {}", e);
+            std::io::Error::other(format!("Migration failed: {}", e))
+        });
+    }
+
+    // One pool and one broker connection shared by every route and subscriber
+    let state = AppState::new(config).await
         .map_err(|e| {
-            log::error!("Failed to connect to database: {}", e);
-            std::io::Error::new(std::io::ErrorKind::Other, format!("Database connection failed: {}", e))
+            log::error!("Failed to initialize application state: {}", e);
+            std::io::Error::other(format!("Startup failed: {}", e))
         })?;
 
-    log::info!("Database connection established successfully");
+    log::info!("Database pool and broker connection established successfully");
+
+    // Bring the schema up to date before anything reads or writes it
+    run_migrations(&state.database).await.map_err(|e| {
+        log::error!("Failed to migrate database: {}", e);
+        std::io::Error::other(format!("Migration failed: {}", e))
+    })?;
 
     // Initialize event subscribers
-    create_init_library_event().await;
-    create_bid_placed_event().await;
-    create_mint_nft_event().await;
-    create_transfer_nft_event().await;
+    create_init_library_event(state.clone()).await;
+    create_bid_placed_event(state.clone()).await;
+    create_mint_nft_event(state.clone()).await;
+    create_transfer_nft_event(state.clone()).await;
 
     // Spawn gRPC subscription task
+    let grpc_state = state.clone();
     tokio::spawn(async move {
-        match transactions_subscribe(PROGRAM_ADDRESS).await {
+        match transactions_subscribe(grpc_state, PROGRAM_ADDRESS).await {
             Ok(_) => {
                 log::info!("Successfully subscribed to transactions");
             }
@@ -65,19 +87,12 @@ async fn main() -> std::io::Result<()> {
         }
     });
 
-    // Create database tables
-    log::info!("Creating database tables if not exists...");
-    create_bid_table_if_not_exists().await;
-    create_winners_table_if_not_exists().await;
-    create_library_table_if_not_exists().await;
-    create_nft_table_if_not_exists().await;
-
     log::info!("Server starting on http://127.0.0.1:8080");
 
     // Start HTTP server
     HttpServer::new(move || {
         App::new()
-            .app_data(web::Data::new(connection.clone()))
+            .app_data(web::Data::new(state.clone()))
             .service(get_placed_bids_route)
             .service(get_placed_bids_by_bidder_route)
             .service(get_winners_route)
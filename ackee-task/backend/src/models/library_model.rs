@@ -1,7 +1,7 @@
-use borsh::BorshDeserialize;
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
+use dapp_client::events::InitLibraryEvent;
 
 #[derive(Debug, Serialize, Deserialize)]
 pub struct InitLibrary {
@@ -11,23 +11,16 @@ pub struct InitLibrary {
     pub library_address: String,
 }
 
-#[derive(Debug, Serialize, Deserialize, BorshDeserialize, Default)]
-pub struct InitLibraryPayload {
-    pub name: String,
-    pub timestamp: i64,
-    pub library_address: [u8; 32],
-}
-
-impl TryFrom<InitLibraryPayload> for InitLibrary {
+impl TryFrom<InitLibraryEvent> for InitLibrary {
     type Error = Box<dyn std::error::Error>;
 
-    fn try_from(item: InitLibraryPayload) -> Result<Self, Self::Error> {
+    fn try_from(item: InitLibraryEvent) -> Result<Self, Self::Error> {
         let timestamp = DateTime::from_timestamp(item.timestamp, 0).ok_or("Invalid timestamp")?;
 
         Ok(Self {
             id: Uuid::now_v7(),
             name: item.name,
-            library_address: bs58::encode(item.library_address).into_string(),
+            library_address: item.library_address.to_string(),
             timestamp: DateTime::from(timestamp),
         })
     }
@@ -1,7 +1,7 @@
-use borsh::BorshDeserialize;
 use chrono::{ DateTime, Utc };
 use serde::{ Deserialize, Serialize };
 use uuid::Uuid;
+use dapp_client::events::MintNftEvent;
 
 #[derive(Debug, Serialize, Deserialize)]
 pub struct MintNft {
@@ -14,30 +14,20 @@ pub struct MintNft {
     pub nft_address: String,
 }
 
-#[derive(Debug, Serialize, Deserialize, BorshDeserialize, Default)]
-pub struct MintNftPayload {
-    pub name: String,
-    pub timestamp: i64,
-    pub library_address: [u8; 32],
-    pub nft_price: u64,
-    pub nft_bid_step: u64,
-    pub nft_address: [u8; 32],
-}
-
-impl TryFrom<MintNftPayload> for MintNft {
+impl TryFrom<MintNftEvent> for MintNft {
     type Error = Box<dyn std::error::Error>;
 
-    fn try_from(item: MintNftPayload) -> Result<Self, Self::Error> {
+    fn try_from(item: MintNftEvent) -> Result<Self, Self::Error> {
         let timestamp = DateTime::from_timestamp(item.timestamp, 0).ok_or("Invalid timestamp")?;
 
         Ok(Self {
             id: Uuid::now_v7(),
             name: item.name,
             timestamp: DateTime::from(timestamp),
-            library_address: bs58::encode(item.library_address).into_string(),
+            library_address: item.library_address.to_string(),
             nft_price: item.nft_price,
             nft_bid_step: item.nft_bid_step,
-            nft_address: bs58::encode(item.nft_address).into_string(),
+            nft_address: item.nft_address.to_string(),
         })
     }
 }
@@ -1,7 +1,7 @@
-use borsh::BorshDeserialize;
 use chrono::{ DateTime, Utc };
 use serde::{ Deserialize, Serialize };
 use uuid::Uuid;
+use dapp_client::events::BidPlaced;
 
 #[derive(Debug, Serialize, Deserialize)]
 pub struct PlacedBids {
@@ -13,26 +13,17 @@ pub struct PlacedBids {
     pub amount: u64,
 }
 
-#[derive(Debug, Serialize, Deserialize, BorshDeserialize, Default)]
-pub struct PlacedBidsPayload {
-    pub nft_name: String,
-    pub nft_address: [u8; 32],
-    pub bidder: [u8; 32],
-    pub amount: u64,
-    pub timestamp: i64,
-}
-
-impl TryFrom<PlacedBidsPayload> for PlacedBids {
+impl TryFrom<BidPlaced> for PlacedBids {
     type Error = Box<dyn std::error::Error>;
 
-    fn try_from(item: PlacedBidsPayload) -> Result<Self, Self::Error> {
+    fn try_from(item: BidPlaced) -> Result<Self, Self::Error> {
         let timestamp = DateTime::from_timestamp(item.timestamp, 0).ok_or("Invalid timestamp")?;
 
         Ok(Self {
             id: Uuid::now_v7(),
             nft_name: item.nft_name,
-            nft_address: bs58::encode(item.nft_address).into_string(),
-            bidder: bs58::encode(item.bidder).into_string(),
+            nft_address: item.nft_address.to_string(),
+            bidder: item.bidder.to_string(),
             amount: item.amount,
             timestamp: DateTime::from(timestamp),
         })
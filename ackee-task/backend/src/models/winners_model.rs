@@ -1,7 +1,7 @@
-use borsh::BorshDeserialize;
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
+use dapp_client::events::TransferNftEvent;
 
 #[derive(Debug, Serialize, Deserialize)]
 pub struct TransferNft {
@@ -12,25 +12,17 @@ pub struct TransferNft {
     pub owner: String,
 }
 
-#[derive(Debug, Serialize, Deserialize, BorshDeserialize, Default)]
-pub struct TransferNftPayload {
-    pub nft_name: String,
-    pub recipient: [u8; 32],
-    pub owner: [u8; 32],
-    pub timestamp: i64,
-}
-
-impl TryFrom<TransferNftPayload> for TransferNft {
+impl TryFrom<TransferNftEvent> for TransferNft {
     type Error = Box<dyn std::error::Error>;
 
-    fn try_from(item: TransferNftPayload) -> Result<Self, Self::Error> {
+    fn try_from(item: TransferNftEvent) -> Result<Self, Self::Error> {
         let timestamp = DateTime::from_timestamp(item.timestamp, 0).ok_or("Invalid timestamp")?;
 
         Ok(Self {
             id: Uuid::now_v7(),
             nft_name: item.nft_name,
-            recipient: bs58::encode(item.recipient).into_string(),
-            owner: bs58::encode(item.owner).into_string(),
+            recipient: item.recipient.to_string(),
+            owner: item.owner.to_string(),
             timestamp: DateTime::from(timestamp),
         })
     }
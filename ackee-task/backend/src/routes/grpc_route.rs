@@ -1,4 +1,4 @@
-use actix_web::{ HttpResponse, get, web::Bytes };
+use actix_web::{ HttpResponse, get, web::{ self, Bytes } };
 
 use serde::Serialize;
 use serde_json;
@@ -6,7 +6,10 @@ use serde_json;
 use futures::stream::StreamExt;
 use tokio::sync::oneshot;
 
-use crate::{ services::{ grpc_service::{ ping_subscribe } } };
+use crate::{
+    services::{ grpc_service::{ ping_subscribe } },
+    structs::app_state_struct::AppState,
+};
 
 struct StreamGuard {
     _guard: tokio::sync::oneshot::Sender<()>,
@@ -25,8 +28,11 @@ struct PingEvent {
 }
 
 #[get("/ping")]
-async fn ping_subscribe_route() -> HttpResponse {
-    let (_subscribe_tx, stream) = ping_subscribe().await.unwrap().subscribe().await.unwrap();
+async fn ping_subscribe_route(state: web::Data<AppState>) -> HttpResponse {
+    let (_subscribe_tx, stream) = ping_subscribe(&state.config).await
+        .unwrap()
+        .subscribe().await
+        .unwrap();
     let (guard_tx, guard_rx) = oneshot::channel();
     let _guard = StreamGuard { _guard: guard_tx };
 
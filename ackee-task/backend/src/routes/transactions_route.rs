@@ -2,12 +2,14 @@ use actix_web::{ HttpResponse, web::{ self, Bytes }, get };
 use futures::StreamExt;
 use tokio::sync::oneshot;
 
-use crate::services::{
-    placed_bids_service::{ get_placed_bids_by_nft_address, get_placed_bids_by_bidder },
-    winners_service::get_winners_by_nft_address,
-    library_service::get_collections_by_address,
-    nft_service::get_nfts_by_collection_address,
-    broker_service::Broker,
+use crate::{
+    services::{
+        placed_bids_service::{ get_placed_bids_by_nft_address, get_placed_bids_by_bidder },
+        winners_service::get_winners_by_nft_address,
+        library_service::get_collections_by_address,
+        nft_service::get_nfts_by_collection_address,
+    },
+    structs::app_state_struct::AppState,
 };
 
 pub static ALL_EVENTS: &str = "all_events";
@@ -63,12 +65,15 @@ impl Drop for StreamGuard {
 }
 
 #[get("/placed-bids/{nft_address}")]
-pub async fn get_placed_bids_route(path: web::Path<String>) -> HttpResponse {
+pub async fn get_placed_bids_route(
+    state: web::Data<AppState>,
+    path: web::Path<String>
+) -> HttpResponse {
     let nft_address = path.into_inner();
 
     validate_solana_input!(&nft_address, 100);
 
-    match get_placed_bids_by_nft_address(nft_address).await {
+    match get_placed_bids_by_nft_address(&state.database, nft_address).await {
         Ok(rows) => HttpResponse::Ok().json(rows),
         Err(err) => {
             log::error!("Database error in get_placed_bids_route: {}", err);
@@ -78,12 +83,15 @@ pub async fn get_placed_bids_route(path: web::Path<String>) -> HttpResponse {
 }
 
 #[get("/placed-bids/bidder/{bidder_address}")]
-pub async fn get_placed_bids_by_bidder_route(path: web::Path<String>) -> HttpResponse {
+pub async fn get_placed_bids_by_bidder_route(
+    state: web::Data<AppState>,
+    path: web::Path<String>
+) -> HttpResponse {
     let bidder_address = path.into_inner();
 
     validate_solana_input!(&bidder_address, 100);
 
-    match get_placed_bids_by_bidder(bidder_address).await {
+    match get_placed_bids_by_bidder(&state.database, bidder_address).await {
         Ok(rows) => HttpResponse::Ok().json(rows),
         Err(err) => {
             log::error!("Database error in get_placed_bids_by_bidder_route: {}", err);
@@ -93,12 +101,15 @@ pub async fn get_placed_bids_by_bidder_route(path: web::Path<String>) -> HttpRes
 }
 
 #[get("/winners/{nft_address}")]
-pub async fn get_winners_route(path: web::Path<String>) -> HttpResponse {
+pub async fn get_winners_route(
+    state: web::Data<AppState>,
+    path: web::Path<String>
+) -> HttpResponse {
     let nft_address = path.into_inner();
 
     validate_input!(&nft_address, 100);
 
-    match get_winners_by_nft_address(nft_address).await {
+    match get_winners_by_nft_address(&state.database, nft_address).await {
         Ok(rows) => HttpResponse::Ok().json(rows),
         Err(err) => {
             log::error!("Database error in get_winners_route: {}", err);
@@ -108,12 +119,15 @@ pub async fn get_winners_route(path: web::Path<String>) -> HttpResponse {
 }
 
 #[get("/collections/{collection_address}")]
-pub async fn get_collections_route(path: web::Path<String>) -> HttpResponse {
+pub async fn get_collections_route(
+    state: web::Data<AppState>,
+    path: web::Path<String>
+) -> HttpResponse {
     let collection_address = path.into_inner();
 
     validate_solana_input!(&collection_address, 100);
 
-    match get_collections_by_address(collection_address).await {
+    match get_collections_by_address(&state.database, collection_address).await {
         Ok(rows) => HttpResponse::Ok().json(rows),
         Err(err) => {
             log::error!("Database error in get_collections_route: {}", err);
@@ -123,12 +137,15 @@ pub async fn get_collections_route(path: web::Path<String>) -> HttpResponse {
 }
 
 #[get("/nfts/{collection_address}")]
-pub async fn get_nfts_by_collection_route(path: web::Path<String>) -> HttpResponse {
+pub async fn get_nfts_by_collection_route(
+    state: web::Data<AppState>,
+    path: web::Path<String>
+) -> HttpResponse {
     let collection_address = path.into_inner();
 
     validate_solana_input!(&collection_address, 100);
 
-    match get_nfts_by_collection_address(collection_address).await {
+    match get_nfts_by_collection_address(&state.database, collection_address).await {
         Ok(rows) => HttpResponse::Ok().json(rows),
         Err(err) => {
             log::error!("Database error in get_nfts_by_collection_route: {}", err);
@@ -138,8 +155,8 @@ pub async fn get_nfts_by_collection_route(path: web::Path<String>) -> HttpRespon
 }
 
 #[get("/events")]
-pub async fn events_sse_route() -> HttpResponse {
-    let subscriber = match Broker::subscribe(ALL_EVENTS).await {
+pub async fn events_sse_route(state: web::Data<AppState>) -> HttpResponse {
+    let subscriber = match state.broker.subscribe(ALL_EVENTS).await {
         Ok(sub) => sub,
         Err(err) => {
             log::error!("Failed to subscribe to events: {}", err);
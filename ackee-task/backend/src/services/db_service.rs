@@ -1,20 +1,21 @@
-use std::env::var;
-use tokio_postgres::{ NoTls, Config };
+use tokio_postgres::{ NoTls, Config as PgConfig };
 use deadpool_postgres::{ Manager, ManagerConfig, Pool, RecyclingMethod, BuildError };
 
+use crate::structs::config_struct::Config;
+
 #[derive(Clone)]
 pub struct Database {
     pub pool: Pool,
 }
 
 impl Database {
-    pub async fn connect() -> Result<Self, BuildError> {
-        let mut pg_config = Config::new();
+    pub fn connect(config: &Config) -> Result<Self, BuildError> {
+        let mut pg_config = PgConfig::new();
 
-        pg_config.host(var("DB_HOST").unwrap_or_else(|_| "127.0.0.1".to_string()));
-        pg_config.dbname(var("DB_NAME").unwrap_or_else(|_| "task".to_string()));
-        pg_config.user(var("DB_USER").unwrap_or_else(|_| "dev".to_string()));
-        pg_config.password(var("DB_PASSWORD").unwrap_or_else(|_| "dev".to_string()));
+        pg_config.host(&config.db_host);
+        pg_config.dbname(&config.db_name);
+        pg_config.user(&config.db_user);
+        pg_config.password(&config.db_password);
 
         let mgr_config = ManagerConfig {
             recycling_method: RecyclingMethod::Fast,
@@ -22,13 +23,7 @@ impl Database {
 
         let mgr = Manager::from_config(pg_config, NoTls, mgr_config);
 
-        // Make pool size configurable
-        let pool_size = var("DB_POOL_SIZE")
-            .ok()
-            .and_then(|s| s.parse().ok())
-            .unwrap_or(16);
-
-        let pool = Pool::builder(mgr).max_size(pool_size).build()?;
+        let pool = Pool::builder(mgr).max_size(config.db_pool_size).build()?;
 
         Ok(Self { pool })
     }
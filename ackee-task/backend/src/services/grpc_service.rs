@@ -1,7 +1,6 @@
 use ::{
-    clap::Parser,
+    dapp_client::utils::Event,
     futures::{ StreamExt },
-    std::env::var,
     tonic::transport::channel::ClientTlsConfig,
     yellowstone_grpc_client::{ GeyserGrpcClient, Interceptor },
     yellowstone_grpc_proto::geyser::{
@@ -13,48 +12,29 @@ use ::{
 
 use crate::{
     services::{
-        broker_service::Broker,
         library_service::INIT_LIBRARY_EVENT,
+        log_parser_service::parse_program_events,
         nft_service::MINT_NFT_EVENT,
         placed_bids_service::BID_PLACED_EVENT,
         winners_service::TRANSFER_NFT_EVENT,
     },
-    structs::transactions_struct::TransactionEvent,
+    structs::{
+        app_state_struct::AppState,
+        config_struct::Config,
+        transactions_struct::TransactionEvent,
+    },
 };
 
-const BID_PLACED_DISCRIMINATOR: [u8; 8] = [209, 98, 122, 16, 194, 244, 76, 183];
-const INIT_LIBRARY_DISCRIMINATOR: [u8; 8] = [225, 117, 68, 160, 27, 168, 128, 51];
-const MINT_NFT_DISCRIMINATOR: [u8; 8] = [211, 57, 6, 167, 15, 219, 35, 251];
-const TRANSFER_NFT_DISCRIMINATOR: [u8; 8] = [190, 28, 194, 8, 194, 218, 78, 78];
-
-#[derive(Parser)]
-struct Args {
-    #[clap(
-        short,
-        long,
-        default_value_t = String::from(
-            var("GEYSER_GRPC_URL").unwrap_or_else(|_| "http://127.0.0.1:10000".to_string())
-        )
-    )]
-    endpoint: String,
-
-    #[clap(long)]
-    x_token: Option<String>,
-}
-
-pub async fn ping_subscribe() -> anyhow::Result<GeyserGrpcClient<impl Interceptor>> {
-    let args = Args::parse();
-
+pub async fn ping_subscribe(config: &Config) -> anyhow::Result<GeyserGrpcClient<impl Interceptor>> {
     Ok(
-        GeyserGrpcClient::build_from_shared(args.endpoint)?
-            .x_token(args.x_token)?
+        GeyserGrpcClient::build_from_shared(config.endpoint.clone())?
+            .x_token(config.x_token.clone())?
             .tls_config(ClientTlsConfig::new().with_native_roots())?
             .connect().await?
     )
 }
 
-pub async fn transactions_subscribe(address: &str) -> anyhow::Result<()> {
-    let args = Args::parse();
+pub async fn transactions_subscribe(state: AppState, address: &str) -> anyhow::Result<()> {
     let mut request = SubscribeRequest::default();
 
     request.transactions.insert("serum".to_string(), SubscribeRequestFilterTransactions {
@@ -66,8 +46,8 @@ pub async fn transactions_subscribe(address: &str) -> anyhow::Result<()> {
         account_required: vec![],
     });
 
-    let mut client = GeyserGrpcClient::build_from_shared(args.endpoint)?
-        .x_token(args.x_token)?
+    let mut client = GeyserGrpcClient::build_from_shared(state.config.endpoint.clone())?
+        .x_token(state.config.x_token.clone())?
         .tls_config(ClientTlsConfig::new().with_native_roots())?
         .connect().await?;
 
@@ -102,60 +82,24 @@ pub async fn transactions_subscribe(address: &str) -> anyhow::Result<()> {
                         };
                         let json = serde_json::to_string(&event).unwrap_or_default();
 
-                        for ix in event.transaction_message.instructions {
-                            if ix.data.len() < 8 {
+                        let mut topics: Vec<&str> = vec![];
+
+                        for program_event in parse_program_events(&event.logs) {
+                            let Some(topic) = event_topic(&program_event) else {
                                 continue;
-                            }
+                            };
 
-                            let discriminator: [u8; 8] = ix.data[..8].try_into().unwrap();
-
-                            match discriminator {
-                                BID_PLACED_DISCRIMINATOR => {
-                                    Broker::publish(
-                                        BID_PLACED_EVENT,
-                                        json.clone().into()
-                                    ).await.ok();
-
-                                    log::info!(
-                                        "{} event published to NATS broker",
-                                        BID_PLACED_EVENT
-                                    );
-                                    break;
-                                }
-                                INIT_LIBRARY_DISCRIMINATOR => {
-                                    Broker::publish(
-                                        INIT_LIBRARY_EVENT,
-                                        json.clone().into()
-                                    ).await.ok();
-
-                                    log::info!(
-                                        "{} event published to NATS broker",
-                                        INIT_LIBRARY_EVENT
-                                    );
-                                    break;
-                                }
-                                MINT_NFT_DISCRIMINATOR => {
-                                    Broker::publish(MINT_NFT_EVENT, json.clone().into()).await.ok();
-
-                                    log::info!("{} event published to NATS broker", MINT_NFT_EVENT);
-                                    break;
-                                }
-                                TRANSFER_NFT_DISCRIMINATOR => {
-                                    Broker::publish(
-                                        TRANSFER_NFT_EVENT,
-                                        json.clone().into()
-                                    ).await.ok();
-
-                                    log::info!(
-                                        "{} event published to NATS broker",
-                                        TRANSFER_NFT_EVENT
-                                    );
-
-                                    break;
-                                }
-                                _ => {}
+                            if !topics.contains(&topic) {
+                                topics.push(topic);
                             }
                         }
+
+                        // Each handler re-parses the logs and saves every event of its kind
+                        for topic in topics {
+                            state.broker.publish(topic, json.clone().into());
+
+                            log::info!("{} event published to NATS broker", topic);
+                        }
                     }
                 }
                 Err(e) => {
@@ -166,3 +110,14 @@ pub async fn transactions_subscribe(address: &str) -> anyhow::Result<()> {
 
     Ok(())
 }
+
+/// NATS topic of the handler that persists `event`, `None` for events the indexer ignores
+fn event_topic(event: &Event) -> Option<&'static str> {
+    match event {
+        Event::BidPlaced(_) => Some(BID_PLACED_EVENT),
+        Event::InitLibraryEvent(_) => Some(INIT_LIBRARY_EVENT),
+        Event::MintNftEvent(_) => Some(MINT_NFT_EVENT),
+        Event::TransferNftEvent(_) => Some(TRANSFER_NFT_EVENT),
+        _ => None,
+    }
+}
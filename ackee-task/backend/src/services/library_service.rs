@@ -1,14 +1,14 @@
 use actix_web::web::Bytes;
-use base64::{ Engine, engine::general_purpose::STANDARD };
-use borsh::BorshDeserialize;
+use dapp_client::utils::Event;
 use futures::StreamExt;
 use serde::Serialize;
 
-use crate::models::library_model::{ InitLibrary, InitLibraryPayload };
+use crate::models::library_model::InitLibrary;
 use crate::routes::transactions_route::ALL_EVENTS;
 use crate::services::db_service::Database;
+use crate::structs::app_state_struct::AppState;
 use crate::structs::transactions_struct::TransactionEvent;
-use crate::services::broker_service::Broker;
+use crate::services::log_parser_service::parse_program_events;
 
 pub static INIT_LIBRARY_EVENT: &str = "init_library";
 
@@ -18,16 +18,18 @@ struct InitLibraryEvent {
     data: InitLibrary,
 }
 
-pub async fn create_init_library_event() {
+pub async fn create_init_library_event(state: AppState) {
     tokio::spawn(async move {
-        match Broker::subscribe(INIT_LIBRARY_EVENT).await {
+        match state.broker.subscribe(INIT_LIBRARY_EVENT).await {
             Ok(broker) => {
+                let state = &state;
+
                 broker.for_each_concurrent(None, |msg| async move {
                     let payload: TransactionEvent = serde_json
                         ::from_slice(&msg.payload)
                         .unwrap_or_default();
 
-                    save_init_library(payload).await;
+                    save_init_library(state, payload).await;
                 }).await;
             }
             Err(err) => {
@@ -37,18 +39,13 @@ pub async fn create_init_library_event() {
     });
 }
 
-pub async fn save_init_library(payload: TransactionEvent) {
-    let database = Database::connect().await.unwrap();
-    let client = database.pool.get().await.expect("Failed to get pool connection");
-
-    let log = payload.logs.iter().find(|log| log.contains("Program data:"));
+pub async fn save_init_library(state: &AppState, payload: TransactionEvent) {
+    let client = state.database.pool.get().await.expect("Failed to get pool connection");
 
-    if let Some(data) = log.and_then(|l| l.strip_prefix("Program data: ")) {
-        let bytes = STANDARD.decode(data).expect("Invalid base64");
-
-        let payload = InitLibraryPayload::try_from_slice(&bytes[8..]).expect(
-            "Failed to deserialize"
-        );
+    for event in parse_program_events(&payload.logs) {
+        let Event::InitLibraryEvent(payload) = event else {
+            continue;
+        };
 
         let library = InitLibrary::try_from(payload).unwrap();
         log::info!("Saving init library event: {:?}", library);
@@ -67,30 +64,15 @@ pub async fn save_init_library(payload: TransactionEvent) {
             data: library,
         };
         if let Ok(json) = serde_json::to_string(&event) {
-            let _ = Broker::publish(ALL_EVENTS, Bytes::from(json)).await;
+            state.broker.publish(ALL_EVENTS, Bytes::from(json));
         }
     }
 }
 
-pub async fn create_library_table_if_not_exists() {
-    let database = Database::connect().await.unwrap();
-
-    let client = database.pool.get().await.expect("Failed to get pool connection");
-    let query =
-        "CREATE TABLE IF NOT EXISTS libraries (
-        id UUID PRIMARY KEY,
-        timestamp TIMESTAMPTZ NOT NULL,
-        name TEXT NOT NULL,
-        library_address TEXT NOT NULL UNIQUE
-    )";
-
-    client.execute(query, &[]).await.unwrap();
-}
-
 pub async fn get_collections_by_address(
+    database: &Database,
     collection_address: String
 ) -> Result<Vec<InitLibrary>, Box<dyn std::error::Error>> {
-    let database = Database::connect().await.unwrap();
     let client = database.pool.get().await.expect("Failed to get pool connection");
 
     let query = "SELECT * FROM libraries WHERE library_address = $1";
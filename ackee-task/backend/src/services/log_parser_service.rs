@@ -0,0 +1,198 @@
+use base64::{ Engine, engine::general_purpose::STANDARD };
+use dapp_client::{ utils::Event, ID };
+
+const PROGRAM_DATA: &str = "Program data: ";
+
+/// Anchor events emitted by our program, in log order
+///
+/// Keeps a stack of invoked programs so `Program data:` lines logged by another program,
+/// whether it CPIs into us or we CPI into it, are never decoded as ours.
+pub fn parse_program_events(logs: &[String]) -> Vec<Event> {
+    let program_id = ID.to_string();
+    let mut invoked: Vec<&str> = vec![];
+    let mut events = vec![];
+
+    for log in logs {
+        if let Some(data) = log.strip_prefix(PROGRAM_DATA) {
+            if invoked.last() != Some(&program_id.as_str()) {
+                continue;
+            }
+
+            match decode_event(data) {
+                Ok(event) => events.push(event),
+                Err(e) => log::warn!("Skipping undecodable program data {}: {}", data, e),
+            }
+
+            continue;
+        }
+
+        let Some(rest) = log.strip_prefix("Program ") else {
+            continue;
+        };
+        let mut parts = rest.split_whitespace();
+
+        match (parts.next(), parts.next()) {
+            (Some(program), Some("invoke")) => invoked.push(program),
+            (Some(program), Some("success" | "failed:")) if invoked.last() == Some(&program) => {
+                invoked.pop();
+            }
+            _ => {}
+        }
+    }
+
+    events
+}
+
+fn decode_event(data: &str) -> anyhow::Result<Event> {
+    let bytes = STANDARD.decode(data)?;
+
+    Event::try_from_bytes(&bytes).map_err(|e| anyhow::anyhow!("{}", e))
+}
+
+#[cfg(test)]
+mod tests {
+    use dapp_client::{
+        anchor_lang::{ prelude::Pubkey, AnchorSerialize, Discriminator },
+        events::{ AuctionSettled, BidPlaced, TransferNftEvent },
+    };
+
+    use super::*;
+
+    const TOKEN_PROGRAM: &str = "TokenkegQfeZyiNwAJbNbGqPFXCWuBvf9Ss623VQ5DA";
+    const METADATA_PROGRAM: &str = "metaqbxxUerdq28cj1RbAWkYQm3ybzjb6a8bt518x1s";
+
+    fn program_data<T: AnchorSerialize + Discriminator>(event: &T) -> String {
+        let mut bytes = T::DISCRIMINATOR.to_vec();
+        event.serialize(&mut bytes).unwrap();
+
+        format!("{}{}", PROGRAM_DATA, STANDARD.encode(bytes))
+    }
+
+    fn bid_placed() -> BidPlaced {
+        BidPlaced {
+            nft_name: "Dune".to_string(),
+            nft_address: Pubkey::new_unique(),
+            bidder: Pubkey::new_unique(),
+            amount: 1_000,
+            timestamp: 1_700_000_000,
+            auction_end_time: 1_700_003_600,
+        }
+    }
+
+    fn logs(lines: &[&str]) -> Vec<String> {
+        lines
+            .iter()
+            .map(|line| line.replace("{dapp}", &ID.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn keeps_every_event_of_a_buy_now_in_order() {
+        let bid = program_data(&bid_placed());
+        let transfer = program_data(
+            &(TransferNftEvent {
+                nft_name: "Dune".to_string(),
+                recipient: Pubkey::new_unique(),
+                owner: Pubkey::new_unique(),
+                timestamp: 1_700_000_000,
+            })
+        );
+        let settled = program_data(
+            &(AuctionSettled {
+                nft_name: "Dune".to_string(),
+                nft_address: Pubkey::new_unique(),
+                seller: Pubkey::new_unique(),
+                winner: Pubkey::new_unique(),
+                final_price: 1_000,
+                royalty_amount: 50,
+                protocol_fee: 10,
+                timestamp: 1_700_000_000,
+            })
+        );
+        let token_invoke = format!("Program {} invoke [2]", TOKEN_PROGRAM);
+        let token_success = format!("Program {} success", TOKEN_PROGRAM);
+
+        let events = parse_program_events(
+            &logs(
+                &[
+                    "Program {dapp} invoke [1]",
+                    "Program log: Instruction: BuyNow",
+                    "Program 11111111111111111111111111111111 invoke [2]",
+                    "Program 11111111111111111111111111111111 success",
+                    &bid,
+                    &token_invoke,
+                    "Program log: Instruction: TransferChecked",
+                    &token_success,
+                    &transfer,
+                    &settled,
+                    "Program {dapp} consumed 80000 of 200000 compute units",
+                    "Program {dapp} success",
+                ]
+            )
+        );
+
+        assert_eq!(events.len(), 3);
+        assert!(matches!(&events[0], Event::BidPlaced(event) if event.amount == 1_000));
+        assert!(matches!(&events[1], Event::TransferNftEvent(_)));
+        assert!(matches!(&events[2], Event::AuctionSettled(event) if event.protocol_fee == 10));
+    }
+
+    #[test]
+    fn only_decodes_data_logged_by_our_program() {
+        let bid = program_data(&bid_placed());
+        // Same bytes, but logged by programs that are not ours
+        let metadata_invoke = format!("Program {} invoke [2]", METADATA_PROGRAM);
+        let metadata_success = format!("Program {} success", METADATA_PROGRAM);
+        let router_invoke = format!("Program {} invoke [1]", TOKEN_PROGRAM);
+        let router_success = format!("Program {} success", TOKEN_PROGRAM);
+
+        let events = parse_program_events(
+            &logs(
+                &[
+                    // Another program CPIs into us, our nested event still counts
+                    &router_invoke,
+                    "Program {dapp} invoke [2]",
+                    &bid,
+                    // We CPI into Metaplex, which emits data of its own
+                    &metadata_invoke,
+                    &bid,
+                    &metadata_success,
+                    "Program {dapp} success",
+                    &bid,
+                    &router_success,
+                ]
+            )
+        );
+
+        assert_eq!(events.len(), 1);
+        assert!(matches!(&events[0], Event::BidPlaced(_)));
+    }
+
+    #[test]
+    fn survives_failed_and_truncated_logs() {
+        let bid = program_data(&bid_placed());
+        let truncated = &bid[..bid.len() / 2];
+        let token_invoke = format!("Program {} invoke [1]", TOKEN_PROGRAM);
+        let token_failed = format!("Program {} failed: custom program error: 0x1", TOKEN_PROGRAM);
+
+        let events = parse_program_events(
+            &logs(
+                &[
+                    // A failed instruction of another program, its data is not ours either
+                    &token_invoke,
+                    &bid,
+                    &token_failed,
+                    "Program {dapp} invoke [1]",
+                    &bid,
+                    truncated,
+                    "Program data: not base64!",
+                    "Log truncated",
+                ]
+            )
+        );
+
+        assert_eq!(events.len(), 1);
+        assert!(matches!(&events[0], Event::BidPlaced(_)));
+        assert!(parse_program_events(&logs(&["Program {dapp} invoke [1]", "Log truncated"])).is_empty());
+    }
+}
@@ -0,0 +1,79 @@
+use crate::services::db_service::Database;
+
+/// Advisory lock key (ASCII `migrate`) that keeps concurrent instances from migrating at once
+const MIGRATION_LOCK: i64 = 0x006d_6967_7261_7465;
+
+struct Migration {
+    version: i32,
+    name: &'static str,
+    sql: &'static str,
+}
+
+/// Applied in order and never edited once released, schema changes go in a new file
+const MIGRATIONS: &[Migration] = &[
+    Migration {
+        version: 1,
+        name: "initial_schema",
+        sql: include_str!("../../migrations/0001_initial_schema.sql"),
+    },
+];
+
+/// Applies every migration newer than the recorded schema version, each in its own transaction
+pub async fn run_migrations(database: &Database) -> anyhow::Result<()> {
+    let mut client = database.pool.get().await?;
+
+    let transaction = client.transaction().await?;
+
+    transaction.execute("SELECT pg_advisory_xact_lock($1)", &[&MIGRATION_LOCK]).await?;
+    transaction.batch_execute(
+        "CREATE TABLE IF NOT EXISTS schema_migrations (
+        version INTEGER PRIMARY KEY,
+        name TEXT NOT NULL,
+        applied_at TIMESTAMPTZ NOT NULL DEFAULT now()
+    )"
+    ).await?;
+    transaction.commit().await?;
+
+    for migration in MIGRATIONS {
+        let transaction = client.transaction().await?;
+
+        transaction.execute("SELECT pg_advisory_xact_lock($1)", &[&MIGRATION_LOCK]).await?;
+
+        let applied = transaction
+            .query_opt(
+                "SELECT 1 FROM schema_migrations WHERE version = $1",
+                &[&migration.version]
+            ).await?
+            .is_some();
+
+        if applied {
+            continue;
+        }
+
+        log::info!("Applying migration {} {}", migration.version, migration.name);
+
+        transaction.batch_execute(migration.sql).await?;
+        transaction.execute(
+            "INSERT INTO schema_migrations (version, name) VALUES ($1, $2)",
+            &[&migration.version, &migration.name]
+        ).await?;
+        transaction.commit().await?;
+    }
+
+    let version = client
+        .query_one("SELECT COALESCE(MAX(version), 0) FROM schema_migrations", &[]).await?
+        .get::<_, i32>(0);
+    let latest = MIGRATIONS.last().map_or(0, |migration| migration.version);
+
+    if version > latest {
+        log::warn!(
+            "Database schema version {} is newer than this build knows about ({})",
+            version,
+            latest
+        );
+    } else {
+        log::info!("Database schema is at version {}", version);
+    }
+
+    Ok(())
+}
@@ -1,6 +1,8 @@
 pub mod db_service;
 pub mod placed_bids_service;
 pub mod grpc_service;
+pub mod log_parser_service;
+pub mod migration_service;
 pub mod broker_service;
 pub mod library_service;
 pub mod nft_service;
@@ -1,14 +1,14 @@
 use actix_web::web::Bytes;
-use base64::{ Engine, engine::general_purpose::STANDARD };
-use borsh::BorshDeserialize;
+use dapp_client::utils::Event;
 use futures::StreamExt;
 use serde::Serialize;
 
-use crate::models::nft_model::{ MintNft, MintNftPayload };
+use crate::models::nft_model::MintNft;
 use crate::routes::transactions_route::ALL_EVENTS;
 use crate::services::db_service::Database;
+use crate::structs::app_state_struct::AppState;
 use crate::structs::transactions_struct::TransactionEvent;
-use crate::services::broker_service::Broker;
+use crate::services::log_parser_service::parse_program_events;
 
 pub static MINT_NFT_EVENT: &str = "mint_nft";
 
@@ -18,16 +18,18 @@ struct MintNftEvent {
     data: MintNft,
 }
 
-pub async fn create_mint_nft_event() {
+pub async fn create_mint_nft_event(state: AppState) {
     tokio::spawn(async move {
-        match Broker::subscribe(MINT_NFT_EVENT).await {
+        match state.broker.subscribe(MINT_NFT_EVENT).await {
             Ok(broker) => {
+                let state = &state;
+
                 broker.for_each_concurrent(None, |msg| async move {
                     let payload: TransactionEvent = serde_json
                         ::from_slice(&msg.payload)
                         .unwrap_or_default();
 
-                    save_mint_nft(payload).await;
+                    save_mint_nft(state, payload).await;
                 }).await;
             }
             Err(err) => {
@@ -37,16 +39,13 @@ pub async fn create_mint_nft_event() {
     });
 }
 
-pub async fn save_mint_nft(payload: TransactionEvent) {
-    let database = Database::connect().await.unwrap();
-    let client = database.pool.get().await.expect("Failed to get pool connection");
-
-    let log = payload.logs.iter().find(|log| log.contains("Program data:"));
+pub async fn save_mint_nft(state: &AppState, payload: TransactionEvent) {
+    let client = state.database.pool.get().await.expect("Failed to get pool connection");
 
-    if let Some(data) = log.and_then(|l| l.strip_prefix("Program data: ")) {
-        let bytes = STANDARD.decode(data).expect("Invalid base64");
-
-        let payload = MintNftPayload::try_from_slice(&bytes[8..]).expect("Failed to deserialize");
+    for event in parse_program_events(&payload.logs) {
+        let Event::MintNftEvent(payload) = event else {
+            continue;
+        };
 
         let nft = MintNft::try_from(payload).unwrap();
         log::info!("Saving mint nft event: {:?}", nft);
@@ -73,38 +72,15 @@ pub async fn save_mint_nft(payload: TransactionEvent) {
             data: nft,
         };
         if let Ok(json) = serde_json::to_string(&event) {
-            let _ = Broker::publish(ALL_EVENTS, Bytes::from(json)).await;
+            state.broker.publish(ALL_EVENTS, Bytes::from(json));
         }
     }
 }
 
-pub async fn create_nft_table_if_not_exists() {
-    let database = Database::connect().await.unwrap();
-
-    let client = database.pool.get().await.expect("Failed to get pool connection");
-    let create_table_query =
-        "CREATE TABLE IF NOT EXISTS nfts (
-        id UUID PRIMARY KEY,
-        timestamp TIMESTAMPTZ NOT NULL,
-        nft_price BIGINT NOT NULL,
-        nft_bid_step BIGINT NOT NULL,
-        name TEXT NOT NULL,
-        library_address TEXT NOT NULL,
-        nft_address TEXT NOT NULL
-    )";
-
-    client.execute(create_table_query, &[]).await.unwrap();
-
-    let create_index_query =
-        "CREATE INDEX IF NOT EXISTS idx_nfts_library_address ON nfts(library_address)";
-
-    client.execute(create_index_query, &[]).await.unwrap();
-}
-
 pub async fn get_nfts_by_collection_address(
+    database: &Database,
     collection_address: String
 ) -> Result<Vec<MintNft>, Box<dyn std::error::Error>> {
-    let database = Database::connect().await.unwrap();
     let client = database.pool.get().await.expect("Failed to get pool connection");
 
     let query = "SELECT * FROM nfts WHERE library_address = $1";
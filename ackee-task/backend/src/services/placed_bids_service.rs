@@ -1,14 +1,14 @@
 use actix_web::web::Bytes;
-use base64::{ Engine, engine::general_purpose::STANDARD };
-use borsh::BorshDeserialize;
+use dapp_client::utils::Event;
 use futures::StreamExt;
 use serde::Serialize;
 
 use crate::models::placed_bids_model::PlacedBids;
 use crate::routes::transactions_route::ALL_EVENTS;
 use crate::services::db_service::Database;
+use crate::structs::app_state_struct::AppState;
 use crate::structs::transactions_struct::TransactionEvent;
-use crate::{ models::placed_bids_model::PlacedBidsPayload, services::broker_service::Broker };
+use crate::services::log_parser_service::parse_program_events;
 
 pub static BID_PLACED_EVENT: &str = "bid_placed";
 
@@ -18,16 +18,18 @@ struct BidPlacedEvent {
     data: PlacedBids,
 }
 
-pub async fn create_bid_placed_event() {
+pub async fn create_bid_placed_event(state: AppState) {
     tokio::spawn(async move {
-        match Broker::subscribe(BID_PLACED_EVENT).await {
+        match state.broker.subscribe(BID_PLACED_EVENT).await {
             Ok(broker) => {
+                let state = &state;
+
                 broker.for_each_concurrent(None, |msg| async move {
                     let payload: TransactionEvent = serde_json
                         ::from_slice(&msg.payload)
                         .unwrap_or_default();
 
-                    save_bid_placed(payload).await;
+                    save_bid_placed(state, payload).await;
                 }).await;
             }
             Err(err) => {
@@ -37,16 +39,8 @@ pub async fn create_bid_placed_event() {
     });
 }
 
-pub async fn save_bid_placed(payload: TransactionEvent) {
-    let database = match Database::connect().await {
-        Ok(db) => db,
-        Err(e) => {
-            log::error!("Failed to connect to database: {}", e);
-            return;
-        }
-    };
-
-    let client = match database.pool.get().await {
+pub async fn save_bid_placed(state: &AppState, payload: TransactionEvent) {
+    let client = match state.database.pool.get().await {
         Ok(client) => client,
         Err(e) => {
             log::error!("Failed to get pool connection: {}", e);
@@ -54,30 +48,16 @@ pub async fn save_bid_placed(payload: TransactionEvent) {
         }
     };
 
-    let log = payload.logs.iter().find(|log| log.contains("Program data:"));
-
-    if let Some(data) = log.and_then(|l| l.strip_prefix("Program data: ")) {
-        let bytes = match STANDARD.decode(data) {
-            Ok(b) => b,
-            Err(e) => {
-                log::error!("Failed to decode base64: {}", e);
-                return;
-            }
-        };
-
-        let payload = match PlacedBidsPayload::try_from_slice(&bytes[8..]) {
-            Ok(p) => p,
-            Err(e) => {
-                log::error!("Failed to deserialize payload: {}", e);
-                return;
-            }
+    for event in parse_program_events(&payload.logs) {
+        let Event::BidPlaced(payload) = event else {
+            continue;
         };
 
         let bid = match PlacedBids::try_from(payload) {
             Ok(b) => b,
             Err(e) => {
                 log::error!("Failed to convert payload to PlacedBids: {}", e);
-                return;
+                continue;
             }
         };
 
@@ -99,7 +79,7 @@ pub async fn save_bid_placed(payload: TransactionEvent) {
             ).await
         {
             log::error!("Failed to insert bid into database: {}", e);
-            return;
+            continue;
         }
 
         // Publish to all_events queue
@@ -108,51 +88,15 @@ pub async fn save_bid_placed(payload: TransactionEvent) {
             data: bid,
         };
         if let Ok(json) = serde_json::to_string(&event) {
-            if let Err(e) = Broker::publish(ALL_EVENTS, Bytes::from(json)).await {
-                log::error!("Failed to publish event to broker: {}", e);
-            }
+            state.broker.publish(ALL_EVENTS, Bytes::from(json));
         }
     }
 }
 
-pub async fn create_bid_table_if_not_exists() {
-    let database = match Database::connect().await {
-        Ok(db) => db,
-        Err(e) => {
-            log::error!("Failed to connect to database: {}", e);
-            return;
-        }
-    };
-
-    let client = match database.pool.get().await {
-        Ok(client) => client,
-        Err(e) => {
-            log::error!("Failed to get pool connection: {}", e);
-            return;
-        }
-    };
-
-    let query =
-        "CREATE TABLE IF NOT EXISTS placed_bids (
-        id UUID PRIMARY KEY,
-        timestamp TIMESTAMPTZ NOT NULL,
-        nft_name TEXT NOT NULL,
-        nft_address TEXT NOT NULL,
-        bidder TEXT NOT NULL,
-        amount BIGINT NOT NULL
-    )";
-
-    if let Err(e) = client.execute(query, &[]).await {
-        log::error!("Failed to create placed_bids table: {}", e);
-    } else {
-        log::info!("placed_bids table created or already exists");
-    }
-}
-
 pub async fn get_placed_bids_by_nft_address(
+    database: &Database,
     address: String
 ) -> Result<Vec<PlacedBids>, Box<dyn std::error::Error>> {
-    let database = Database::connect().await?;
     let client = database.pool.get().await
         .map_err(|e| format!("Failed to get pool connection: {}", e))?;
 
@@ -163,9 +107,9 @@ pub async fn get_placed_bids_by_nft_address(
 }
 
 pub async fn get_placed_bids_by_bidder(
+    database: &Database,
     bidder: String
 ) -> Result<Vec<PlacedBids>, Box<dyn std::error::Error>> {
-    let database = Database::connect().await?;
     let client = database.pool.get().await
         .map_err(|e| format!("Failed to get pool connection: {}", e))?;
 
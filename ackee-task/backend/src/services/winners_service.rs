@@ -1,14 +1,14 @@
 use actix_web::web::Bytes;
-use base64::{ Engine, engine::general_purpose::STANDARD };
-use borsh::BorshDeserialize;
+use dapp_client::utils::Event;
 use futures::StreamExt;
 use serde::Serialize;
 
-use crate::models::winners_model::{ TransferNft, TransferNftPayload };
+use crate::models::winners_model::TransferNft;
 use crate::routes::transactions_route::ALL_EVENTS;
 use crate::services::db_service::Database;
+use crate::structs::app_state_struct::AppState;
 use crate::structs::transactions_struct::TransactionEvent;
-use crate::services::broker_service::Broker;
+use crate::services::log_parser_service::parse_program_events;
 
 pub static TRANSFER_NFT_EVENT: &str = "transfer_nft";
 
@@ -18,16 +18,18 @@ struct TransferNftEvent {
     data: TransferNft,
 }
 
-pub async fn create_transfer_nft_event() {
+pub async fn create_transfer_nft_event(state: AppState) {
     tokio::spawn(async move {
-        match Broker::subscribe(TRANSFER_NFT_EVENT).await {
+        match state.broker.subscribe(TRANSFER_NFT_EVENT).await {
             Ok(broker) => {
+                let state = &state;
+
                 broker.for_each_concurrent(None, |msg| async move {
                     let payload: TransactionEvent = serde_json
                         ::from_slice(&msg.payload)
                         .unwrap_or_default();
 
-                    save_transfer_nft(payload).await;
+                    save_transfer_nft(state, payload).await;
                 }).await;
             }
             Err(err) => {
@@ -37,16 +39,8 @@ pub async fn create_transfer_nft_event() {
     });
 }
 
-pub async fn save_transfer_nft(payload: TransactionEvent) {
-    let database = match Database::connect().await {
-        Ok(db) => db,
-        Err(e) => {
-            log::error!("Failed to connect to database: {}", e);
-            return;
-        }
-    };
-
-    let client = match database.pool.get().await {
+pub async fn save_transfer_nft(state: &AppState, payload: TransactionEvent) {
+    let client = match state.database.pool.get().await {
         Ok(client) => client,
         Err(e) => {
             log::error!("Failed to get pool connection: {}", e);
@@ -54,30 +48,16 @@ pub async fn save_transfer_nft(payload: TransactionEvent) {
         }
     };
 
-    let log = payload.logs.iter().find(|log| log.contains("Program data:"));
-
-    if let Some(data) = log.and_then(|l| l.strip_prefix("Program data: ")) {
-        let bytes = match STANDARD.decode(data) {
-            Ok(b) => b,
-            Err(e) => {
-                log::error!("Failed to decode base64: {}", e);
-                return;
-            }
-        };
-
-        let payload = match TransferNftPayload::try_from_slice(&bytes[8..]) {
-            Ok(p) => p,
-            Err(e) => {
-                log::error!("Failed to deserialize payload: {}", e);
-                return;
-            }
+    for event in parse_program_events(&payload.logs) {
+        let Event::TransferNftEvent(payload) = event else {
+            continue;
         };
 
         let transfer = match TransferNft::try_from(payload) {
             Ok(t) => t,
             Err(e) => {
                 log::error!("Failed to convert payload to TransferNft: {}", e);
-                return;
+                continue;
             }
         };
 
@@ -98,7 +78,7 @@ pub async fn save_transfer_nft(payload: TransactionEvent) {
             ).await
         {
             log::error!("Failed to insert transfer into database: {}", e);
-            return;
+            continue;
         }
 
         // Publish to all_events queue
@@ -107,50 +87,15 @@ pub async fn save_transfer_nft(payload: TransactionEvent) {
             data: transfer,
         };
         if let Ok(json) = serde_json::to_string(&event) {
-            if let Err(e) = Broker::publish(ALL_EVENTS, Bytes::from(json)).await {
-                log::error!("Failed to publish event to broker: {}", e);
-            }
+            state.broker.publish(ALL_EVENTS, Bytes::from(json));
         }
     }
 }
 
-pub async fn create_winners_table_if_not_exists() {
-    let database = match Database::connect().await {
-        Ok(db) => db,
-        Err(e) => {
-            log::error!("Failed to connect to database: {}", e);
-            return;
-        }
-    };
-
-    let client = match database.pool.get().await {
-        Ok(client) => client,
-        Err(e) => {
-            log::error!("Failed to get pool connection: {}", e);
-            return;
-        }
-    };
-
-    let query =
-        "CREATE TABLE IF NOT EXISTS winners (
-        id UUID PRIMARY KEY,
-        timestamp TIMESTAMPTZ NOT NULL,
-        nft_name TEXT NOT NULL,
-        recipient TEXT NOT NULL,
-        owner TEXT NOT NULL
-    )";
-
-    if let Err(e) = client.execute(query, &[]).await {
-        log::error!("Failed to create winners table: {}", e);
-    } else {
-        log::info!("winners table created or already exists");
-    }
-}
-
 pub async fn get_winners_by_nft_address(
+    database: &Database,
     nft_address: String
 ) -> Result<Vec<TransferNft>, Box<dyn std::error::Error>> {
-    let database = Database::connect().await?;
     let client = database.pool.get().await
         .map_err(|e| format!("Failed to get pool connection: {}", e))?;
 
@@ -0,0 +1,21 @@
+use crate::{
+    services::{ broker_service::Broker, db_service::Database },
+    structs::config_struct::Config,
+};
+
+/// Shared by every route and subscriber so the process holds one pool and one NATS connection
+#[derive(Clone)]
+pub struct AppState {
+    pub config: Config,
+    pub database: Database,
+    pub broker: Broker,
+}
+
+impl AppState {
+    pub async fn new(config: Config) -> anyhow::Result<Self> {
+        let database = Database::connect(&config)?;
+        let broker = Broker::connect(&config.nats_url).await?;
+
+        Ok(Self { config, database, broker })
+    }
+}
@@ -0,0 +1,60 @@
+use std::env::var;
+use clap::{ Parser, Subcommand };
+
+/// Indexer settings, each flag falls back to its environment variable
+#[derive(Parser, Clone)]
+pub struct Config {
+    #[clap(subcommand)]
+    pub command: Option<Command>,
+
+    #[clap(
+        short,
+        long,
+        default_value_t = String::from(
+            var("GEYSER_GRPC_URL").unwrap_or_else(|_| "http://127.0.0.1:10000".to_string())
+        )
+    )]
+    pub endpoint: String,
+
+    #[clap(long)]
+    pub x_token: Option<String>,
+
+    #[clap(
+        long,
+        default_value_t = String::from(
+            var("NATS_URL").unwrap_or_else(|_| "nats://localhost:4222".to_string())
+        )
+    )]
+    pub nats_url: String,
+
+    #[clap(long, default_value_t = var("DB_HOST").unwrap_or_else(|_| "127.0.0.1".to_string()))]
+    pub db_host: String,
+
+    #[clap(long, default_value_t = var("DB_NAME").unwrap_or_else(|_| "task".to_string()))]
+    pub db_name: String,
+
+    #[clap(long, default_value_t = var("DB_USER").unwrap_or_else(|_| "dev".to_string()))]
+    pub db_user: String,
+
+    #[clap(
+        long,
+        hide_default_value = true,
+        default_value_t = var("DB_PASSWORD").unwrap_or_else(|_| "dev".to_string())
+    )]
+    pub db_password: String,
+
+    #[clap(
+        long,
+        default_value_t = var("DB_POOL_SIZE")
+            .ok()
+            .and_then(|s| s.parse().ok())
+            .unwrap_or(16)
+    )]
+    pub db_pool_size: usize,
+}
+
+#[derive(Subcommand, Clone)]
+pub enum Command {
+    /// Apply pending database migrations and exit
+    Migrate,
+}
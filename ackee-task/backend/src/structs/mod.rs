@@ -1 +1,3 @@
 pub mod transactions_struct;
+pub mod config_struct;
+pub mod app_state_struct;
@@ -0,0 +1,328 @@
+use std::{ fmt::Write, time::{ SystemTime, UNIX_EPOCH } };
+
+use anchor_lang::{
+    prelude::{ Clock, Pubkey, Rent },
+    solana_program::{ program_pack::Pack, sysvar },
+};
+use anchor_spl::{
+    associated_token::get_associated_token_address_with_program_id,
+    token::spl_token,
+};
+use dapp::{ AuctionState, AuctionType, EscrowBidder, NftInfo };
+use dapp_client::pda::NftPdas;
+
+use crate::{
+    decode::{ auction_type, decode_as, field, gate, optional },
+    source::{ AccountSource, RawAccount },
+};
+
+/// Human readable state of one auction, the summary ops need without decoding every field
+pub fn report(
+    source: &AccountSource,
+    library_name: &str,
+    name: &str,
+    now: Option<i64>
+) -> anyhow::Result<String> {
+    let pdas = NftPdas::derive(library_name, name);
+    let addresses = [pdas.nft_info.0, pdas.auction_state.0, pdas.bidder_escrow.0, sysvar::clock::ID];
+    let mut accounts = source.get_accounts(&addresses)?.into_iter();
+    let (nft_info, auction_state, escrow, clock) = (
+        accounts.next().flatten(),
+        accounts.next().flatten(),
+        accounts.next().flatten(),
+        accounts.next().flatten(),
+    );
+
+    let now = now.or_else(|| clock.as_ref().and_then(clock_timestamp)).unwrap_or_else(system_time);
+    let mut out = String::new();
+
+    writeln!(out, "{} in library {}", name, library_name)?;
+    field(&mut out, "nft_info", pdas.nft_info.0)?;
+    field(&mut out, "auction_state", pdas.auction_state.0)?;
+    field(&mut out, "escrow", pdas.bidder_escrow.0)?;
+
+    let Some(nft_info) = decode_as::<NftInfo>(nft_info.as_ref())? else {
+        field(&mut out, "status", "no NftInfo, never minted or already settled")?;
+
+        return Ok(out);
+    };
+    let auction_state = decode_as::<AuctionState>(auction_state.as_ref())?;
+    let unit = match nft_info.payment_mint {
+        Some(mint) => format!("of mint {}", mint),
+        None => "lamports".to_string(),
+    };
+    let amount = |amount: u64| format!("{} {}", amount, unit);
+
+    let Some(auction_state) = auction_state else {
+        field(&mut out, "status", "minted, auction not started")?;
+        field(&mut out, "starting price", amount(nft_info.starting_price))?;
+
+        return Ok(out);
+    };
+
+    let has_bids = nft_info.current_bidder != auction_state.seller;
+    let unsold = nft_info.is_unsold(&auction_state.seller);
+
+    field(&mut out, "type", auction_type(&auction_state.auction_type))?;
+    field(&mut out, "status", status(&auction_state, unsold, now))?;
+    field(&mut out, "seller", auction_state.seller)?;
+
+    let price = auction_state.dutch_price(now).unwrap_or(nft_info.current_price);
+    field(&mut out, "current price", amount(price))?;
+
+    match auction_state.auction_type {
+        AuctionType::English => {
+            let min_bid = if has_bids {
+                nft_info.current_price.saturating_add(nft_info.bid_step)
+            } else {
+                nft_info.starting_price
+            };
+            field(&mut out, "minimum bid", amount(min_bid))?;
+        }
+        AuctionType::Sealed { .. } if has_bids => {
+            field(&mut out, "runner-up bid", amount(auction_state.runner_up_price))?;
+        }
+        _ => {}
+    }
+
+    let leader = match auction_state.auction_type {
+        _ if has_bids => nft_info.current_bidder.to_string(),
+        AuctionType::Sealed { .. } => "none revealed".to_string(),
+        _ => "none".to_string(),
+    };
+    field(&mut out, "leader", leader)?;
+    field(&mut out, "reserve price", optional(nft_info.reserve_price.map(amount)))?;
+    field(&mut out, "buy now price", optional(nft_info.buy_now_price.map(amount)))?;
+    field(&mut out, "gate", optional(auction_state.gate.as_ref().map(gate)))?;
+
+    if let Some(escrow) = &escrow {
+        let balance = escrow_balance(source, escrow, &pdas.bidder_escrow.0, nft_info.payment_mint)?;
+        field(&mut out, "escrow balance", amount(balance))?;
+
+        if let Some(escrow_bidder) = decode_as::<EscrowBidder>(Some(escrow))? {
+            field(&mut out, "rent payer", escrow_bidder.payer)?;
+        }
+    }
+
+    Ok(out)
+}
+
+fn status(auction_state: &AuctionState, unsold: bool, now: i64) -> String {
+    if !auction_state.has_started(now) {
+        return format!("scheduled, starts in {}", duration(auction_state.auction_start_time - now));
+    }
+
+    if now < auction_state.auction_end_time {
+        let phase = match auction_state.auction_type {
+            AuctionType::Sealed { .. } => "taking sealed bids",
+            _ => "live",
+        };
+
+        return format!("{}, {} left", phase, duration(auction_state.auction_end_time - now));
+    }
+
+    if auction_state.is_revealing(now) {
+        return format!("revealing, {} left", duration(auction_state.ends_at() - now));
+    }
+
+    let outcome = if unsold {
+        "unsold, waiting for relist or withdraw_unsold"
+    } else {
+        "awaiting settlement"
+    };
+
+    format!("ended {} ago, {}", duration(now - auction_state.ends_at()), outcome)
+}
+
+/// Funds locked by bidders, the rent exempt reserve of a native escrow is left out
+fn escrow_balance(
+    source: &AccountSource,
+    escrow: &RawAccount,
+    escrow_address: &Pubkey,
+    payment_mint: Option<Pubkey>
+) -> anyhow::Result<u64> {
+    let Some(payment_mint) = payment_mint else {
+        let rent = Rent::default().minimum_balance(escrow.data.len());
+
+        return Ok(escrow.lamports.saturating_sub(rent));
+    };
+
+    // The escrow ATA is derived under whichever token program owns the payment mint
+    let Some(mint) = source.get_accounts(&[payment_mint])?.pop().flatten() else {
+        return Ok(0);
+    };
+    let escrow_payment_account = get_associated_token_address_with_program_id(
+        escrow_address,
+        &payment_mint,
+        &mint.owner
+    );
+
+    Ok(
+        source
+            .get_accounts(&[escrow_payment_account])?
+            .pop()
+            .flatten()
+            .map_or(0, |account| token_amount(&account.data))
+    )
+}
+
+// Token-2022 accounts share the SPL token layout, their extensions come after it
+fn token_amount(data: &[u8]) -> u64 {
+    data.get(..spl_token::state::Account::LEN)
+        .and_then(|base| spl_token::state::Account::unpack_from_slice(base).ok())
+        .map_or(0, |account| account.amount)
+}
+
+fn clock_timestamp(clock: &RawAccount) -> Option<i64> {
+    bincode::deserialize::<Clock>(&clock.data).ok().map(|clock| clock.unix_timestamp)
+}
+
+fn system_time() -> i64 {
+    SystemTime::now().duration_since(UNIX_EPOCH).map_or(0, |elapsed| elapsed.as_secs() as i64)
+}
+
+fn duration(seconds: i64) -> String {
+    let (days, hours, minutes, seconds) = (
+        seconds / 86_400,
+        (seconds % 86_400) / 3_600,
+        (seconds % 3_600) / 60,
+        seconds % 60,
+    );
+
+    match (days, hours, minutes) {
+        (0, 0, 0) => format!("{}s", seconds),
+        (0, 0, _) => format!("{}m {}s", minutes, seconds),
+        (0, _, _) => format!("{}h {}m", hours, minutes),
+        _ => format!("{}d {}h", days, hours),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{ fs, path::PathBuf };
+
+    use anchor_lang::{ prelude::Clock, solana_program::program_option::COption, AccountSerialize };
+    use anchor_spl::token::spl_token::{ self, state::{ Account, AccountState } };
+    use base64::{ engine::general_purpose::STANDARD, Engine };
+    use dapp::{ AuctionState, AuctionType, EscrowBidder, NftInfo };
+    use serde_json::json;
+
+    use super::*;
+
+    const NOW: i64 = 1_700_000_000;
+
+    fn account_data<T: AccountSerialize>(account: &T) -> Vec<u8> {
+        let mut data = Vec::new();
+        account.try_serialize(&mut data).unwrap();
+        data
+    }
+
+    /// Writes the accounts the way `solana account --output json` dumps them
+    fn dump(file: &str, accounts: Vec<(Pubkey, Pubkey, u64, Vec<u8>)>) -> AccountSource {
+        let entries: Vec<_> = accounts
+            .into_iter()
+            .map(|(address, owner, lamports, data)| {
+                json!({
+                    "pubkey": address.to_string(),
+                    "account": {
+                        "lamports": lamports,
+                        "data": [STANDARD.encode(&data), "base64"],
+                        "owner": owner.to_string(),
+                        "executable": false,
+                        "rentEpoch": 18446744073709551615u64,
+                        "space": data.len(),
+                    },
+                })
+            })
+            .collect();
+        let path: PathBuf = std::env::temp_dir().join(format!("{}-{}.json", file, std::process::id()));
+
+        fs::write(&path, serde_json::to_string(&entries).unwrap()).unwrap();
+        let source = AccountSource::from_dump(&path).unwrap();
+        fs::remove_file(&path).unwrap();
+
+        source
+    }
+
+    #[test]
+    fn reports_a_dumped_token_auction() {
+        let (seller, bidder, payment_mint) = (
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+        );
+        let pdas = NftPdas::derive("Books", "Dune");
+        let escrow_payment_account = get_associated_token_address_with_program_id(
+            &pdas.bidder_escrow.0,
+            &payment_mint,
+            &spl_token::ID
+        );
+
+        let nft_info = NftInfo {
+            current_price: 700,
+            starting_price: 500,
+            current_bidder: bidder,
+            bid_step: 100,
+            reserve_price: None,
+            buy_now_price: None,
+            payment_mint: Some(payment_mint),
+            bump: pdas.nft_info.1,
+            library_name: "Books".to_string(),
+            name: "Dune".to_string(),
+            nft_mint: Pubkey::new_unique(),
+            round: 0,
+        };
+        let auction_state = AuctionState {
+            nft_mint: nft_info.nft_mint,
+            seller,
+            auction_start_time: NOW - 60,
+            auction_end_time: NOW + 90,
+            extension_window: 0,
+            auction_type: AuctionType::English,
+            bump: pdas.auction_state.1,
+            gate: None,
+            runner_up_price: 0,
+        };
+        let escrow = EscrowBidder { payer: seller, bump: pdas.bidder_escrow.1 };
+
+        let mut token_account = vec![0; Account::LEN];
+        Account::pack(
+            Account {
+                mint: payment_mint,
+                owner: pdas.bidder_escrow.0,
+                amount: 700,
+                delegate: COption::None,
+                state: AccountState::Initialized,
+                is_native: COption::None,
+                delegated_amount: 0,
+                close_authority: COption::None,
+            },
+            &mut token_account
+        ).unwrap();
+        let clock = bincode::serialize(&Clock { unix_timestamp: NOW, ..Clock::default() }).unwrap();
+
+        let source = dump("dapp-inspector-auction", vec![
+            (pdas.nft_info.0, dapp::ID, 1, account_data(&nft_info)),
+            (pdas.auction_state.0, dapp::ID, 1, account_data(&auction_state)),
+            (pdas.bidder_escrow.0, dapp::ID, 1, account_data(&escrow)),
+            (payment_mint, spl_token::ID, 1, vec![0; spl_token::state::Mint::LEN]),
+            (escrow_payment_account, spl_token::ID, 1, token_account),
+            (sysvar::clock::ID, sysvar::ID, 1, clock),
+        ]);
+
+        let report = report(&source, "Books", "Dune", None).unwrap();
+        let line = |name: &str| {
+            report
+                .lines()
+                .find(|line| line.trim_start().starts_with(name))
+                .map(|line| line[name.len() + 2..].trim().to_string())
+                .unwrap()
+        };
+
+        // The time left comes from the dumped clock, not the machine running the test
+        assert_eq!(line("status"), "live, 1m 30s left");
+        assert_eq!(line("leader"), bidder.to_string());
+        assert_eq!(line("minimum bid"), format!("800 of mint {}", payment_mint));
+        assert_eq!(line("escrow balance"), format!("700 of mint {}", payment_mint));
+    }
+}
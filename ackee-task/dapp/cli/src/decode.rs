@@ -0,0 +1,130 @@
+use std::fmt;
+
+use anchor_lang::{ AccountDeserialize, Discriminator };
+use anyhow::{ anyhow, bail };
+use dapp::{ AuctionGate, AuctionState, AuctionType, EscrowBidder, NftInfo };
+
+use crate::source::RawAccount;
+
+/// A program account identified by its 8-byte Anchor discriminator
+pub enum DappAccount {
+    NftInfo(NftInfo),
+    AuctionState(AuctionState),
+    EscrowBidder(EscrowBidder),
+}
+
+impl DappAccount {
+    pub fn decode(data: &[u8]) -> anyhow::Result<Self> {
+        if data.starts_with(NftInfo::DISCRIMINATOR) {
+            Ok(DappAccount::NftInfo(deserialize(data)?))
+        } else if data.starts_with(AuctionState::DISCRIMINATOR) {
+            Ok(DappAccount::AuctionState(deserialize(data)?))
+        } else if data.starts_with(EscrowBidder::DISCRIMINATOR) {
+            Ok(DappAccount::EscrowBidder(deserialize(data)?))
+        } else {
+            bail!("unknown discriminator {:?}", &data[..data.len().min(8)])
+        }
+    }
+}
+
+/// Decodes `account` as `T`, `None` when it does not exist
+pub fn decode_as<T: AccountDeserialize>(account: Option<&RawAccount>) -> anyhow::Result<Option<T>> {
+    account
+        .map(|account| {
+            check_owner(account)?;
+            deserialize(&account.data)
+        })
+        .transpose()
+}
+
+pub fn check_owner(account: &RawAccount) -> anyhow::Result<()> {
+    if account.owner != dapp::ID {
+        bail!("account is owned by {}, not the dapp program", account.owner);
+    }
+
+    Ok(())
+}
+
+fn deserialize<T: AccountDeserialize>(mut data: &[u8]) -> anyhow::Result<T> {
+    T::try_deserialize(&mut data).map_err(|err| anyhow!("{}", err))
+}
+
+impl fmt::Display for DappAccount {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            DappAccount::NftInfo(nft_info) => {
+                writeln!(f, "NftInfo")?;
+                field(f, "library_name", &nft_info.library_name)?;
+                field(f, "name", &nft_info.name)?;
+                field(f, "nft_mint", nft_info.nft_mint)?;
+                field(f, "current_price", nft_info.current_price)?;
+                field(f, "starting_price", nft_info.starting_price)?;
+                field(f, "current_bidder", nft_info.current_bidder)?;
+                field(f, "bid_step", nft_info.bid_step)?;
+                field(f, "reserve_price", optional(nft_info.reserve_price))?;
+                field(f, "buy_now_price", optional(nft_info.buy_now_price))?;
+                field(f, "payment_mint", optional(nft_info.payment_mint))?;
+                field(f, "round", nft_info.round)?;
+                field(f, "bump", nft_info.bump)
+            }
+            DappAccount::AuctionState(auction_state) => {
+                writeln!(f, "AuctionState")?;
+                field(f, "nft_mint", auction_state.nft_mint)?;
+                field(f, "seller", auction_state.seller)?;
+                field(f, "auction_start_time", auction_state.auction_start_time)?;
+                field(f, "auction_end_time", auction_state.auction_end_time)?;
+                field(f, "extension_window", auction_state.extension_window)?;
+                field(f, "auction_type", auction_type(&auction_state.auction_type))?;
+                field(f, "gate", optional(auction_state.gate.as_ref().map(gate)))?;
+                field(f, "runner_up_price", auction_state.runner_up_price)?;
+                field(f, "bump", auction_state.bump)
+            }
+            DappAccount::EscrowBidder(escrow) => {
+                writeln!(f, "EscrowBidder")?;
+                field(f, "payer", escrow.payer)?;
+                field(f, "bump", escrow.bump)
+            }
+        }
+    }
+}
+
+pub fn field(f: &mut impl fmt::Write, name: &str, value: impl fmt::Display) -> fmt::Result {
+    writeln!(f, "  {:<20} {}", name, value)
+}
+
+pub fn optional(value: Option<impl fmt::Display>) -> String {
+    value.map_or_else(|| "-".to_string(), |value| value.to_string())
+}
+
+pub fn auction_type(auction_type: &AuctionType) -> String {
+    match auction_type {
+        AuctionType::English => "English".to_string(),
+        AuctionType::Dutch { start_price, floor_price, price_drop, drop_interval } =>
+            format!(
+                "Dutch from {} to {}, dropping {} every {}s",
+                start_price,
+                floor_price,
+                price_drop,
+                drop_interval
+            ),
+        AuctionType::Sealed { reveal_window, second_price } =>
+            format!(
+                "Sealed {}, {}s reveal window",
+                if *second_price { "second price" } else { "first price" },
+                reveal_window
+            ),
+    }
+}
+
+pub fn gate(gate: &AuctionGate) -> String {
+    match gate {
+        AuctionGate::TokenHolder { mint } => format!("holders of mint {}", mint),
+        AuctionGate::CollectionHolder { collection } =>
+            format!("holders of collection {}", collection),
+        AuctionGate::Allowlist { merkle_root } =>
+            format!(
+                "allowlist with root {}",
+                merkle_root.iter().map(|byte| format!("{:02x}", byte)).collect::<String>()
+            ),
+    }
+}
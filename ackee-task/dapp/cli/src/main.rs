@@ -0,0 +1,133 @@
+use std::path::PathBuf;
+
+use anchor_lang::prelude::Pubkey;
+use anyhow::anyhow;
+use base64::{ engine::general_purpose::STANDARD, Engine };
+use clap::{ Parser, Subcommand };
+use dapp_client::pda::{ BidderPdas, LibraryPdas, NftPdas };
+
+use crate::{
+    decode::{ check_owner, field, DappAccount },
+    source::{ parse_pubkey, AccountSource },
+};
+
+mod auction;
+mod decode;
+mod source;
+
+/// Decodes dapp auction accounts so nobody has to read raw base64
+#[derive(Parser)]
+struct Args {
+    /// JSON-RPC endpoint of the validator
+    #[clap(short, long, global = true, default_value = "http://127.0.0.1:8899")]
+    url: String,
+
+    /// `solana account --output json` dump, or a directory of them, read instead of the validator
+    #[clap(long, global = true)]
+    dump: Option<PathBuf>,
+
+    #[clap(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Current price, leader, time remaining and escrow balance of an NFT auction
+    Auction {
+        library: String,
+        name: String,
+
+        /// Unix timestamp to evaluate the auction at, defaults to the cluster clock
+        #[clap(long)]
+        now: Option<i64>,
+    },
+    /// Decodes an NftInfo, AuctionState or EscrowBidder account
+    Account {
+        address: String,
+    },
+    /// Decodes base64 account data copied from an explorer or RPC response
+    Decode {
+        data: String,
+    },
+    /// Derives the program addresses of a library, one of its NFTs and a bidder
+    Pdas {
+        library: String,
+        name: Option<String>,
+
+        #[clap(long, requires = "name")]
+        bidder: Option<String>,
+    },
+}
+
+fn main() -> anyhow::Result<()> {
+    let args = Args::parse();
+    let source = || match &args.dump {
+        Some(path) => AccountSource::from_dump(path),
+        None => Ok(AccountSource::Rpc(args.url.clone())),
+    };
+
+    match &args.command {
+        Command::Auction { library, name, now } => {
+            print!("{}", auction::report(&source()?, library, name, *now)?);
+        }
+        Command::Account { address } => {
+            let account = source()?
+                .get_accounts(&[parse_pubkey(address)?])?
+                .pop()
+                .flatten()
+                .ok_or_else(|| anyhow!("account {} not found", address))?;
+
+            check_owner(&account)?;
+
+            let mut out = DappAccount::decode(&account.data)?.to_string();
+            field(&mut out, "lamports", account.lamports)?;
+            print!("{}", out);
+        }
+        Command::Decode { data } => {
+            print!("{}", DappAccount::decode(&STANDARD.decode(data.trim())?)?);
+        }
+        Command::Pdas { library, name, bidder } => {
+            print_pdas(library, name.as_deref(), bidder.as_deref())?;
+        }
+    }
+
+    Ok(())
+}
+
+fn print_pdas(library: &str, name: Option<&str>, bidder: Option<&str>) -> anyhow::Result<()> {
+    let pda = |label: &str, (address, bump): (Pubkey, u8)| {
+        println!("  {:<24} {} (bump {})", label, address, bump);
+    };
+
+    let library_pdas = LibraryPdas::derive(library);
+    println!("Library {}", library);
+    pda("library", library_pdas.library);
+    pda("library_config", library_pdas.library_config);
+    pda("library_mint", library_pdas.library_mint);
+    pda("library_account", library_pdas.library_account);
+    pda("library_metadata", library_pdas.library_metadata);
+    pda("library_master_edition", library_pdas.library_master_edition);
+
+    let Some(name) = name else {
+        return Ok(());
+    };
+
+    let nft_pdas = NftPdas::derive(library, name);
+    println!("NFT {}", name);
+    pda("nft_info", nft_pdas.nft_info);
+    pda("nft_mint", nft_pdas.nft_mint);
+    pda("auction_state", nft_pdas.auction_state);
+    pda("bidder_escrow", nft_pdas.bidder_escrow);
+    pda("nft_metadata", nft_pdas.nft_metadata);
+    pda("nft_master_edition", nft_pdas.nft_master_edition);
+    println!("  {:<24} {}", "escrow_nft_account", nft_pdas.escrow_nft_account);
+
+    if let Some(bidder) = bidder {
+        let bidder_pdas = BidderPdas::derive(library, name, &parse_pubkey(bidder)?);
+        println!("Bidder {}", bidder);
+        pda("pending_refund", bidder_pdas.pending_refund);
+        pda("sealed_bid", bidder_pdas.sealed_bid);
+    }
+
+    Ok(())
+}
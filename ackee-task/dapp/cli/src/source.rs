@@ -0,0 +1,133 @@
+use std::{ collections::HashMap, fs, path::Path, str::FromStr };
+
+use anchor_lang::prelude::Pubkey;
+use anyhow::{ anyhow, bail, Context };
+use base64::{ engine::general_purpose::STANDARD, Engine };
+use serde::Deserialize;
+use serde_json::{ json, Value };
+
+/// Account as returned by `getAccountInfo` or written by `solana account --output json`
+#[derive(Deserialize)]
+struct UiAccount {
+    lamports: u64,
+    data: (String, String),
+    owner: String,
+}
+
+/// One entry of a `solana account --output json` dump, as read by `solana-test-validator --account-dir`
+#[derive(Deserialize)]
+struct KeyedUiAccount {
+    pubkey: String,
+    account: UiAccount,
+}
+
+#[derive(Clone)]
+pub struct RawAccount {
+    pub lamports: u64,
+    pub owner: Pubkey,
+    pub data: Vec<u8>,
+}
+
+pub enum AccountSource {
+    Rpc(String),
+    Dump(HashMap<Pubkey, RawAccount>),
+}
+
+impl AccountSource {
+    /// Reads a dump file holding one account or an array of them, or a directory of such files
+    pub fn from_dump(path: &Path) -> anyhow::Result<Self> {
+        let mut accounts = HashMap::new();
+
+        let files = if path.is_dir() {
+            let mut files = fs
+                ::read_dir(path)?
+                .map(|entry| Ok(entry?.path()))
+                .collect::<anyhow::Result<Vec<_>>>()?;
+            files.retain(|file| file.extension().is_some_and(|extension| extension == "json"));
+            files
+        } else {
+            vec![path.to_path_buf()]
+        };
+
+        for file in files {
+            let contents = fs
+                ::read_to_string(&file)
+                .with_context(|| format!("reading {}", file.display()))?;
+            let value: Value = serde_json
+                ::from_str(&contents)
+                .with_context(|| format!("parsing {}", file.display()))?;
+
+            let entries: Vec<KeyedUiAccount> = match value {
+                Value::Array(_) => serde_json::from_value(value)?,
+                _ => vec![serde_json::from_value(value)?],
+            };
+
+            for entry in entries {
+                accounts.insert(parse_pubkey(&entry.pubkey)?, entry.account.decode()?);
+            }
+        }
+
+        Ok(AccountSource::Dump(accounts))
+    }
+
+    /// Fetches all `addresses` in one request, missing accounts come back as `None`
+    pub fn get_accounts(&self, addresses: &[Pubkey]) -> anyhow::Result<Vec<Option<RawAccount>>> {
+        match self {
+            AccountSource::Rpc(url) => {
+                let addresses: Vec<String> = addresses.iter().map(ToString::to_string).collect();
+                let response: Value = ureq
+                    ::post(url)
+                    .send_json(
+                        json!({
+                            "jsonrpc": "2.0",
+                            "id": 1,
+                            "method": "getMultipleAccounts",
+                            "params": [addresses, { "encoding": "base64" }],
+                        })
+                    )
+                    .with_context(|| format!("requesting accounts from {}", url))?
+                    .into_json()?;
+
+                if let Some(error) = response.get("error") {
+                    bail!("RPC error: {}", error);
+                }
+
+                let accounts: Vec<Option<UiAccount>> = serde_json::from_value(
+                    response["result"]["value"].clone()
+                )?;
+
+                accounts
+                    .into_iter()
+                    .map(|account| account.map(UiAccount::decode).transpose())
+                    .collect()
+            }
+            AccountSource::Dump(accounts) =>
+                Ok(
+                    addresses
+                        .iter()
+                        .map(|address| accounts.get(address).cloned())
+                        .collect()
+                ),
+        }
+    }
+}
+
+impl UiAccount {
+    fn decode(self) -> anyhow::Result<RawAccount> {
+        let (data, encoding) = self.data;
+
+        if encoding != "base64" {
+            bail!("unsupported account encoding {}, dump with --output json", encoding);
+        }
+
+        Ok(RawAccount {
+            lamports: self.lamports,
+            owner: parse_pubkey(&self.owner)?,
+            data: STANDARD.decode(data)?,
+        })
+    }
+}
+
+pub fn parse_pubkey(value: &str) -> anyhow::Result<Pubkey> {
+    Pubkey::from_str(value).map_err(|_| anyhow!("invalid address {}", value))
+}
@@ -0,0 +1,33 @@
+//! Typed client for the `dapp` program, generated from `idls/dapp.json` by `declare_program!`.
+//!
+//! Regenerate the IDL with `yarn idl` whenever an instruction, account or event changes, so
+//! the backend and tooling never hand-copy a layout.
+
+// The generated CPI helpers take one argument per instruction argument
+#![allow(clippy::too_many_arguments)]
+use anchor_lang::{
+    prelude::*,
+    solana_program::instruction::Instruction,
+    InstructionData,
+    ToAccountMetas,
+};
+
+pub use anchor_lang;
+
+declare_program!(dapp);
+
+pub use self::dapp::{ accounts, client, constants, events, types, utils, ID };
+
+pub mod pda;
+
+/// Builds a `dapp` instruction from the generated `client::accounts` and `client::args` structs
+pub fn instruction(
+    accounts: impl ToAccountMetas,
+    args: impl InstructionData,
+    remaining_accounts: Vec<AccountMeta>
+) -> Instruction {
+    let mut metas = accounts.to_account_metas(None);
+    metas.extend(remaining_accounts);
+
+    Instruction::new_with_bytes(ID, &args.data(), metas)
+}
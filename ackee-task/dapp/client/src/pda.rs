@@ -0,0 +1,119 @@
+use anchor_lang::prelude::Pubkey;
+use anchor_spl::{
+    associated_token::get_associated_token_address_with_program_id,
+    metadata::mpl_token_metadata,
+    token::ID as TOKEN_PROGRAM_ID,
+};
+use crate::constants::{
+    AUCTION_STATE_SEEDS,
+    BIDDER_ESCROW_SEEDS,
+    BUID_NFT_SEEDS,
+    LIBRARY_ACCOUNT_SEEDS,
+    LIBRARY_CONFIG_SEEDS,
+    LIBRARY_MASTER_EDITION_SEEDS,
+    LIBRARY_METADATA_SEEDS,
+    LIBRARY_MINT_SEEDS,
+    LIBRARY_SEEDS,
+    NFT_MINT_SEEDS,
+    PENDING_REFUND_SEEDS,
+    SEALED_BID_SEEDS,
+};
+
+/// Addresses derived with the same seeds as the `#[account(seeds = ...)]` constraints
+pub struct LibraryPdas {
+    pub library: (Pubkey, u8),
+    pub library_config: (Pubkey, u8),
+    pub library_mint: (Pubkey, u8),
+    pub library_account: (Pubkey, u8),
+    pub library_metadata: (Pubkey, u8),
+    pub library_master_edition: (Pubkey, u8),
+}
+
+pub struct NftPdas {
+    pub nft_info: (Pubkey, u8),
+    pub nft_mint: (Pubkey, u8),
+    pub auction_state: (Pubkey, u8),
+    pub bidder_escrow: (Pubkey, u8),
+    pub nft_metadata: (Pubkey, u8),
+    pub nft_master_edition: (Pubkey, u8),
+    // ATA holding the NFT while it is listed, assumes the classic token program
+    pub escrow_nft_account: Pubkey,
+}
+
+pub struct BidderPdas {
+    pub pending_refund: (Pubkey, u8),
+    pub sealed_bid: (Pubkey, u8),
+}
+
+impl LibraryPdas {
+    pub fn derive(library_name: &str) -> Self {
+        let library_mint = find(&[LIBRARY_MINT_SEEDS, library_name.as_bytes()]);
+
+        Self {
+            library: find(&[LIBRARY_SEEDS, library_name.as_bytes()]),
+            library_config: find(&[LIBRARY_CONFIG_SEEDS, library_name.as_bytes()]),
+            library_account: find(&[LIBRARY_ACCOUNT_SEEDS, library_name.as_bytes()]),
+            library_metadata: metadata(&library_mint.0),
+            library_master_edition: master_edition(&library_mint.0),
+            library_mint,
+        }
+    }
+}
+
+impl NftPdas {
+    pub fn derive(library_name: &str, name: &str) -> Self {
+        let seeds = |prefix: &[u8]| find(&[prefix, library_name.as_bytes(), name.as_bytes()]);
+        let nft_mint = seeds(NFT_MINT_SEEDS);
+        let bidder_escrow = seeds(BIDDER_ESCROW_SEEDS);
+
+        Self {
+            nft_info: seeds(BUID_NFT_SEEDS),
+            auction_state: seeds(AUCTION_STATE_SEEDS),
+            nft_metadata: metadata(&nft_mint.0),
+            nft_master_edition: master_edition(&nft_mint.0),
+            escrow_nft_account: get_associated_token_address_with_program_id(
+                &bidder_escrow.0,
+                &nft_mint.0,
+                &TOKEN_PROGRAM_ID
+            ),
+            nft_mint,
+            bidder_escrow,
+        }
+    }
+}
+
+impl BidderPdas {
+    pub fn derive(library_name: &str, name: &str, bidder: &Pubkey) -> Self {
+        let seeds = |prefix: &[u8]| {
+            find(&[prefix, library_name.as_bytes(), name.as_bytes(), bidder.as_ref()])
+        };
+
+        Self {
+            pending_refund: seeds(PENDING_REFUND_SEEDS),
+            sealed_bid: seeds(SEALED_BID_SEEDS),
+        }
+    }
+}
+
+fn find(seeds: &[&[u8]]) -> (Pubkey, u8) {
+    Pubkey::find_program_address(seeds, &crate::ID)
+}
+
+fn metadata(mint: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[LIBRARY_METADATA_SEEDS, mpl_token_metadata::ID.as_ref(), mint.as_ref()],
+        &mpl_token_metadata::ID
+    )
+}
+
+fn master_edition(mint: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[
+            LIBRARY_METADATA_SEEDS,
+            mpl_token_metadata::ID.as_ref(),
+            mint.as_ref(),
+            LIBRARY_MASTER_EDITION_SEEDS,
+        ],
+        &mpl_token_metadata::ID
+    )
+}
@@ -0,0 +1,155 @@
+//! Fails when `idls/dapp.json` no longer matches the program, run `yarn idl` to regenerate it
+
+use anchor_lang::{ prelude::Pubkey, AccountSerialize, Discriminator, Event as _ };
+use dapp_client::utils::{ Account, Event };
+
+macro_rules! assert_discriminators {
+    ($client:path => $program:path, $($name:ident),* $(,)?) => {{
+        use $client as generated;
+        use $program as program;
+
+        $(
+            assert_eq!(
+                <generated::$name as Discriminator>::DISCRIMINATOR,
+                <program::$name as Discriminator>::DISCRIMINATOR,
+                stringify!($name)
+            );
+        )*
+    }};
+}
+
+#[test]
+fn instruction_discriminators_match_the_program() {
+    assert_discriminators!(
+        dapp_client::client::args => dapp::instruction,
+        BatchMintNft,
+        BidNft,
+        BuyNow,
+        CancelAuction,
+        CommitBid,
+        InitLibrary,
+        MigrateLibrary,
+        MigrateNft,
+        MintNft,
+        Relist,
+        RescheduleAuction,
+        RevealBid,
+        RotateLibraryAuthority,
+        SettleAuction,
+        StartAuction,
+        TransferNft,
+        UpdateLibrary,
+        UpdateNftMetadata,
+        WithdrawRefund,
+        WithdrawUnsold,
+    );
+}
+
+#[test]
+fn account_discriminators_match_the_program() {
+    assert_discriminators!(
+        dapp_client::accounts => dapp,
+        AuctionState,
+        EscrowBidder,
+        Library,
+        LibraryConfig,
+        NftInfo,
+        PendingRefund,
+        SealedBid,
+    );
+}
+
+#[test]
+fn event_discriminators_match_the_program() {
+    assert_discriminators!(
+        dapp_client::events => dapp,
+        AuctionCancelled,
+        AuctionRelisted,
+        AuctionRescheduled,
+        AuctionSettled,
+        BidCommitted,
+        BidPlaced,
+        BidRevealed,
+        InitLibraryEvent,
+        LibraryAuthorityRotated,
+        LibraryMigrated,
+        LibraryUpdated,
+        MintNftEvent,
+        NftMetadataUpdated,
+        NftMigrated,
+        RefundWithdrawn,
+        TransferNftEvent,
+        UnsoldWithdrawn,
+    );
+}
+
+#[test]
+fn indexed_events_decode_what_the_program_emits() {
+    let (nft_address, bidder) = (Pubkey::new_unique(), Pubkey::new_unique());
+
+    let bid_placed = dapp::BidPlaced {
+        nft_name: "Dune".to_string(),
+        nft_address,
+        bidder,
+        amount: 1_000,
+        timestamp: 1_700_000_000,
+        auction_end_time: 1_700_003_600,
+    };
+    let Ok(Event::BidPlaced(decoded)) = Event::try_from_bytes(&bid_placed.data()) else {
+        panic!("BidPlaced did not decode");
+    };
+    assert_eq!(decoded.nft_name, "Dune");
+    assert_eq!((decoded.nft_address, decoded.bidder), (nft_address, bidder));
+    assert_eq!((decoded.amount, decoded.auction_end_time), (1_000, 1_700_003_600));
+
+    let mint_nft = dapp::MintNftEvent {
+        name: "Dune".to_string(),
+        timestamp: 1_700_000_000,
+        library_address: bidder,
+        nft_price: 1_000,
+        nft_bid_step: 100,
+        nft_address,
+    };
+    let Ok(Event::MintNftEvent(decoded)) = Event::try_from_bytes(&mint_nft.data()) else {
+        panic!("MintNftEvent did not decode");
+    };
+    assert_eq!((decoded.nft_bid_step, decoded.nft_address), (100, nft_address));
+
+    let transfer_nft = dapp::TransferNftEvent {
+        nft_name: "Dune".to_string(),
+        recipient: bidder,
+        owner: nft_address,
+        timestamp: 1_700_000_000,
+    };
+    let Ok(Event::TransferNftEvent(decoded)) = Event::try_from_bytes(&transfer_nft.data()) else {
+        panic!("TransferNftEvent did not decode");
+    };
+    assert_eq!((decoded.recipient, decoded.owner), (bidder, nft_address));
+}
+
+#[test]
+fn nft_info_decodes_what_the_program_stores() {
+    let nft_info = dapp::NftInfo {
+        current_price: 1_500,
+        starting_price: 1_000,
+        current_bidder: Pubkey::new_unique(),
+        bid_step: 100,
+        reserve_price: Some(2_000),
+        buy_now_price: None,
+        payment_mint: None,
+        bump: 254,
+        library_name: "Books".to_string(),
+        name: "Dune".to_string(),
+        nft_mint: Pubkey::new_unique(),
+        round: 3,
+    };
+    let mut data = vec![];
+    nft_info.try_serialize(&mut data).unwrap();
+
+    let Ok(Account::NftInfo(decoded)) = Account::try_from_bytes(&data) else {
+        panic!("NftInfo did not decode");
+    };
+    assert_eq!(decoded.current_bidder, nft_info.current_bidder);
+    assert_eq!(decoded.reserve_price, Some(2_000));
+    assert_eq!(decoded.nft_mint, nft_info.nft_mint);
+}
@@ -6,12 +6,21 @@ pub const LIBRARY_MINT_SEEDS: &[u8] = b"library_mint";
 #[constant]
 pub const LIBRARY_ACCOUNT_SEEDS: &[u8] = b"library_account";
 
+#[constant]
+pub const LIBRARY_SEEDS: &[u8] = b"library";
+
+#[constant]
+pub const LIBRARY_CONFIG_SEEDS: &[u8] = b"library_config";
+
 #[constant]
 pub const LIBRARY_METADATA_SEEDS: &[u8] = b"metadata";
 
 #[constant]
 pub const LIBRARY_MASTER_EDITION_SEEDS: &[u8] = b"edition";
 
+#[constant]
+pub const NFT_MINT_SEEDS: &[u8] = b"nft_mint";
+
 #[constant]
 pub const BUID_NFT_SEEDS: &[u8] = b"nft_info";
 
@@ -22,14 +31,36 @@ pub const AUCTION_STATE_SEEDS: &[u8] = b"auction";
 pub const BIDDER_ESCROW_SEEDS: &[u8] = b"bidder_escrow";
 
 #[constant]
-pub const NAME: &str = "ZZ Tokens";
+pub const PENDING_REFUND_SEEDS: &[u8] = b"pending_refund";
+
+#[constant]
+pub const SEALED_BID_SEEDS: &[u8] = b"sealed_bid";
+
+#[constant]
+pub const MAX_BASIS_POINTS: u16 = 10_000;
+
+// A third item overflows the 64-entry instruction trace of a transaction. Two items with short
+// names and URIs fit a 1232-byte legacy transaction, longer ones need an address lookup table.
+#[constant]
+pub const MAX_BATCH_MINT_ITEMS: u8 = 2;
+
+#[constant]
+pub const MAX_LIBRARY_DELEGATES: u8 = 5;
+
+#[constant]
+pub const MAX_LIBRARY_FIELD_LEN: u8 = 200;
 
 #[constant]
 pub const SYMBOL: &str = "ZZ";
 
 #[constant]
-pub const COLLECTION_URL: &str =
-    "https://raw.githubusercontent.com/ibakalovLc/solana/main/metadata.json";
+pub const MAX_NAME_LEN: u8 = 32;
+
+#[constant]
+pub const MAX_SYMBOL_LEN: u8 = 10;
+
+#[constant]
+pub const MAX_URI_LEN: u8 = 200;
 
 #[constant]
-pub const NFT_URL: &str = "https://raw.githubusercontent.com/ibakalovLc/solana/main/nft.json";
+pub const MAX_CREATORS: u8 = 5;
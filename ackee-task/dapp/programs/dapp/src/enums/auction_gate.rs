@@ -0,0 +1,18 @@
+use anchor_lang::prelude::*;
+
+/// Restricts who may bid on an auction, checked by `bid_nft` and `buy_now`
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq, InitSpace)]
+pub enum AuctionGate {
+    /// Holders of any amount of a token mint
+    TokenHolder {
+        mint: Pubkey,
+    },
+    /// Holders of an NFT from a verified Metaplex collection
+    CollectionHolder {
+        collection: Pubkey,
+    },
+    /// Wallets in a merkle tree of sha256 leaves of the bidder key, pairs hashed in sorted order
+    Allowlist {
+        merkle_root: [u8; 32],
+    },
+}
@@ -0,0 +1,18 @@
+use anchor_lang::prelude::*;
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq, InitSpace)]
+pub enum AuctionType {
+    English,
+    Dutch {
+        start_price: u64,
+        floor_price: u64,
+        price_drop: u64,
+        drop_interval: i64,
+    },
+    /// Bids are committed until `auction_end_time` and revealed during the following `reveal_window`,
+    /// the winner pays the runner-up bid when `second_price` is set
+    Sealed {
+        reveal_window: i64,
+        second_price: bool,
+    },
+}
@@ -44,4 +44,72 @@ pub enum ErrorCode {
     InvalidPreviousBidder,
     #[msg("Invalid account owner")]
     InvalidAccountOwner,
+    #[msg("Unauthorized cancel")]
+    UnauthorizedCancel,
+    #[msg("Invalid reserve price")]
+    InvalidReservePrice,
+    #[msg("Invalid rent payer")]
+    InvalidRentPayer,
+    #[msg("Invalid extension window")]
+    InvalidExtensionWindow,
+    #[msg("Invalid dutch auction schedule")]
+    InvalidDutchAuction,
+    #[msg("Reserve price not met")]
+    ReservePriceNotMet,
+    #[msg("Invalid buy now price")]
+    InvalidBuyNowPrice,
+    #[msg("Buy now is not available")]
+    BuyNowNotAvailable,
+    #[msg("Invalid payment mint")]
+    InvalidPaymentMint,
+    #[msg("Invalid payment account")]
+    InvalidPaymentAccount,
+    #[msg("Invalid fee basis points")]
+    InvalidFeeBasisPoints,
+    #[msg("Invalid protocol fee account")]
+    InvalidProtocolFeeAccount,
+    #[msg("Invalid creator account")]
+    InvalidCreatorAccount,
+    #[msg("Invalid legacy account")]
+    InvalidLegacyAccount,
+    #[msg("Unauthorized library authority")]
+    UnauthorizedLibraryAuthority,
+    #[msg("Too many library delegates")]
+    TooManyLibraryDelegates,
+    #[msg("Library field too long")]
+    LibraryFieldTooLong,
+    #[msg("Metadata field too long")]
+    MetadataFieldTooLong,
+    #[msg("Invalid creator shares")]
+    InvalidCreatorShares,
+    #[msg("Invalid batch mint accounts")]
+    InvalidBatchMint,
+    #[msg("Invalid pending refund")]
+    InvalidPendingRefund,
+    #[msg("Highest bid cannot be withdrawn")]
+    RefundLocked,
+    #[msg("Auction has a winning bid")]
+    AuctionHasWinner,
+    #[msg("Auction has not started yet")]
+    AuctionNotStarted,
+    #[msg("Auction has already started")]
+    AuctionAlreadyStarted,
+    #[msg("Bidder does not pass the auction gate")]
+    GateNotSatisfied,
+    #[msg("Sealed auctions only take committed bids")]
+    SealedBidsOnly,
+    #[msg("Auction does not take sealed bids")]
+    NotSealedAuction,
+    #[msg("Reveal phase is not active")]
+    RevealNotActive,
+    #[msg("Reveal does not match the commitment")]
+    InvalidReveal,
+    #[msg("Fees exceed the final price")]
+    FeesExceedFinalPrice,
+    #[msg("Sealed bid was committed in an earlier round")]
+    StaleSealedBid,
+    #[msg("Only the program upgrade authority can migrate a library")]
+    UnauthorizedMigration,
+    #[msg("Sellers cannot bid on their own lot")]
+    SellerCannotBid,
 }
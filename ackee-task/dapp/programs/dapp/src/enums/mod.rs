@@ -1,2 +1,8 @@
 pub mod error_codes;
 pub use error_codes::*;
+
+pub mod auction_type;
+pub use auction_type::*;
+
+pub mod auction_gate;
+pub use auction_gate::*;
@@ -0,0 +1,275 @@
+use anchor_lang::{
+    prelude::*,
+    system_program::{
+        allocate,
+        assign,
+        create_account,
+        transfer,
+        Allocate,
+        Assign,
+        CreateAccount,
+        Transfer,
+    },
+};
+use anchor_lang::solana_program::program_pack::Pack;
+use anchor_spl::{
+    metadata::Metadata,
+    associated_token::{ AssociatedToken, create, Create },
+    token::spl_token,
+    token_interface::{ Mint, InitializeMint2, TokenAccount, TokenInterface, initialize_mint2 },
+};
+
+use crate::enums::ErrorCode;
+use crate::state::{ EscrowBidder, Library, LibraryConfig, NftInfo };
+use crate::{ constants::*, MintNftItem, NftMintAccounts, _mint_nft_item };
+
+// nft_info, nft_mint, nft_bidder_escrow, associated_nft, nft_metadata, nft_master_edition
+const ACCOUNTS_PER_ITEM: usize = 6;
+
+/// Mints several lamport-priced NFTs into a library, the per-item accounts come in as remaining accounts
+#[derive(Accounts)]
+#[instruction(library_name: String)]
+pub struct BatchMintNft<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    #[account(mut, seeds = [LIBRARY_ACCOUNT_SEEDS, library_name.as_bytes()], bump)]
+    pub library_account: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(mut, seeds = [LIBRARY_MINT_SEEDS, library_name.as_bytes()], bump)]
+    pub library_mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        mut,
+        seeds = [
+            LIBRARY_METADATA_SEEDS,
+            metadata_program.key().as_ref(),
+            library_mint.key().as_ref()
+        ],
+        seeds::program = metadata_program.key(),
+        bump
+    )]
+    /// CHECK: This account is validated by the Metaplex Token Metadata program using PDA seeds
+    pub library_metadata: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [
+            LIBRARY_METADATA_SEEDS,
+            metadata_program.key().as_ref(),
+            library_mint.key().as_ref(),
+            LIBRARY_MASTER_EDITION_SEEDS,
+        ],
+        bump,
+        seeds::program = metadata_program.key()
+    )]
+    /// CHECK: This account is validated by the Metaplex Token Metadata program as the master edition PDA
+    pub library_master_edition: UncheckedAccount<'info>,
+
+    #[account(
+        seeds = [LIBRARY_SEEDS, library_name.as_bytes()],
+        bump = library.bump,
+        constraint = library.can_mint(&payer.key()) @ ErrorCode::UnauthorizedLibraryAuthority
+    )]
+    pub library: Account<'info, Library>,
+
+    #[account(
+        seeds = [LIBRARY_CONFIG_SEEDS, library_name.as_bytes()],
+        bump = library_config.bump
+    )]
+    pub library_config: Account<'info, LibraryConfig>,
+
+    pub system_program: Program<'info, System>,
+    pub token_program: Interface<'info, TokenInterface>,
+    pub metadata_program: Program<'info, Metadata>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+pub fn _batch_mint_nft<'info>(
+    ctx: &mut Context<'_, '_, '_, 'info, BatchMintNft<'info>>,
+    library_name: String,
+    items: Vec<MintNftItem>
+) -> Result<()> {
+    require!(
+        !items.is_empty() && items.len() <= (MAX_BATCH_MINT_ITEMS as usize),
+        ErrorCode::InvalidBatchMint
+    );
+    require!(
+        ctx.remaining_accounts.len() == items.len() * ACCOUNTS_PER_ITEM,
+        ErrorCode::InvalidBatchMint
+    );
+
+    let signer_seeds: &[&[&[u8]]] = &[
+        &[LIBRARY_MINT_SEEDS, library_name.as_bytes(), &[ctx.bumps.library_mint]],
+    ];
+
+    let payer = ctx.accounts.payer.to_account_info();
+    let system_program = ctx.accounts.system_program.to_account_info();
+    let token_program = ctx.accounts.token_program.to_account_info();
+
+    for (item, item_accounts) in items
+        .into_iter()
+        .zip(ctx.remaining_accounts.chunks(ACCOUNTS_PER_ITEM)) {
+        let [nft_info, nft_mint, nft_bidder_escrow, associated_nft, nft_metadata, nft_master_edition] =
+            item_accounts else {
+            return err!(ErrorCode::InvalidBatchMint);
+        };
+
+        item.validate()?;
+
+        let name = item.name.as_bytes();
+
+        let nft_info_bump = _create_pda_account(
+            &payer,
+            nft_info,
+            &[BUID_NFT_SEEDS, library_name.as_bytes(), name],
+            8 + NftInfo::INIT_SPACE,
+            &crate::ID,
+            &system_program
+        )?;
+        _create_pda_account(
+            &payer,
+            nft_mint,
+            &[NFT_MINT_SEEDS, library_name.as_bytes(), name],
+            spl_token::state::Mint::LEN,
+            token_program.key,
+            &system_program
+        )?;
+        let nft_bidder_escrow_bump = _create_pda_account(
+            &payer,
+            nft_bidder_escrow,
+            &[BIDDER_ESCROW_SEEDS, library_name.as_bytes(), name],
+            8 + EscrowBidder::INIT_SPACE,
+            &crate::ID,
+            &system_program
+        )?;
+
+        initialize_mint2(
+            CpiContext::new(token_program.to_account_info(), InitializeMint2 {
+                mint: nft_mint.to_account_info(),
+            }),
+            0,
+            &ctx.accounts.library_mint.key(),
+            Some(&ctx.accounts.library_mint.key())
+        )?;
+
+        item
+            .nft_info(&library_name, payer.key(), nft_mint.key(), None, nft_info_bump)
+            .try_serialize(&mut &mut nft_info.try_borrow_mut_data()?[..])?;
+
+        (EscrowBidder {
+            payer: payer.key(),
+            bump: nft_bidder_escrow_bump,
+        }).try_serialize(&mut &mut nft_bidder_escrow.try_borrow_mut_data()?[..])?;
+
+        create(
+            CpiContext::new(ctx.accounts.associated_token_program.to_account_info(), Create {
+                payer: payer.to_account_info(),
+                associated_token: associated_nft.to_account_info(),
+                authority: nft_bidder_escrow.to_account_info(),
+                mint: nft_mint.to_account_info(),
+                system_program: system_program.to_account_info(),
+                token_program: token_program.to_account_info(),
+            })
+        )?;
+
+        _mint_nft_item(
+            &(NftMintAccounts {
+                payer: &payer,
+                nft_mint,
+                associated_nft,
+                nft_metadata,
+                nft_master_edition,
+                library_account: &ctx.accounts.library_account.to_account_info(),
+                library_mint: &ctx.accounts.library_mint.to_account_info(),
+                library_metadata: &ctx.accounts.library_metadata.to_account_info(),
+                library_master_edition: &ctx.accounts.library_master_edition.to_account_info(),
+                seller_fee_basis_points: ctx.accounts.library_config.seller_fee_basis_points,
+                token_program: &token_program,
+                metadata_program: &ctx.accounts.metadata_program.to_account_info(),
+                system_program: &system_program,
+                rent: &ctx.accounts.rent.to_account_info(),
+            }),
+            signer_seeds,
+            item
+        )?;
+    }
+
+    Ok(())
+}
+
+/// Same as an Anchor `init` on a PDA, returns the bump after checking the address.
+/// Anyone can send lamports to the address beforehand, `create_account` would then fail,
+/// so a funded account is topped up to rent exemption, allocated and assigned instead.
+fn _create_pda_account<'info>(
+    payer: &AccountInfo<'info>,
+    account: &AccountInfo<'info>,
+    seeds: &[&[u8]],
+    space: usize,
+    owner: &Pubkey,
+    system_program: &AccountInfo<'info>
+) -> Result<u8> {
+    let (address, bump) = Pubkey::find_program_address(seeds, &crate::ID);
+
+    require_keys_eq!(account.key(), address, ErrorCode::InvalidBatchMint);
+
+    let bump_seed = [bump];
+    let signer_seeds: &[&[&[u8]]] = &[&[seeds, &[&bump_seed[..]]].concat()];
+    let rent = Rent::get()?.minimum_balance(space).max(1);
+    let current_lamports = account.lamports();
+
+    if current_lamports == 0 {
+        create_account(
+            CpiContext::new_with_signer(
+                system_program.to_account_info(),
+                CreateAccount {
+                    from: payer.to_account_info(),
+                    to: account.to_account_info(),
+                },
+                signer_seeds
+            ),
+            rent,
+            space as u64,
+            owner
+        )?;
+
+        return Ok(bump);
+    }
+
+    let required_lamports = rent.saturating_sub(current_lamports);
+
+    if required_lamports > 0 {
+        transfer(
+            CpiContext::new(system_program.to_account_info(), Transfer {
+                from: payer.to_account_info(),
+                to: account.to_account_info(),
+            }),
+            required_lamports
+        )?;
+    }
+
+    allocate(
+        CpiContext::new_with_signer(
+            system_program.to_account_info(),
+            Allocate {
+                account_to_allocate: account.to_account_info(),
+            },
+            signer_seeds
+        ),
+        space as u64
+    )?;
+
+    assign(
+        CpiContext::new_with_signer(
+            system_program.to_account_info(),
+            Assign {
+                account_to_assign: account.to_account_info(),
+            },
+            signer_seeds
+        ),
+        owner
+    )?;
+
+    Ok(bump)
+}
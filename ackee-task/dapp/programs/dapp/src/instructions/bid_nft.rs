@@ -1,55 +1,123 @@
-use anchor_lang::{ prelude::*, system_program };
-use anchor_lang::system_program::{ transfer, Transfer };
+use anchor_lang::prelude::*;
 
 use anchor_spl::{
-    associated_token::AssociatedToken,
-    metadata::Metadata,
+    associated_token::{ AssociatedToken, create_idempotent, Create },
+    metadata::{ Metadata, MetadataAccount },
     token_interface::{ Mint, TokenAccount, TokenInterface },
 };
 
-use crate::enums::ErrorCode;
-use crate::state::{ AuctionState, NftInfo };
-use crate::{ constants::*, EscrowBidder };
+use crate::enums::{ AuctionType, ErrorCode };
+use crate::state::{ AuctionState, LibraryConfig, NftInfo, PendingRefund };
+use crate::{ constants::*, EscrowBidder, SettlementAccounts, _common_settlement_logic };
+use crate::utils::{ FeeAccounts, GateProof, _check_gate, _lock_bid, _token_payment };
 
 #[derive(Accounts)]
-#[instruction(name: String)]
+#[instruction(name: String, library_name: String)]
 pub struct BidNft<'info> {
     #[account(mut)]
     pub payer: Signer<'info>,
 
-    #[account(mut, seeds = [BUID_NFT_SEEDS, name.as_bytes()], bump)]
+    #[account(mut, seeds = [BUID_NFT_SEEDS, library_name.as_bytes(), name.as_bytes()], bump)]
     pub nft_info: Account<'info, NftInfo>,
 
-    #[account(mut, seeds = [AUCTION_STATE_SEEDS, nft_info.name.as_bytes()], bump)]
+    #[account(mut, seeds = [AUCTION_STATE_SEEDS, library_name.as_bytes(), name.as_bytes()], bump)]
     pub auction_state: Account<'info, AuctionState>,
 
-    #[account(
-        mut,
-        seeds = [name.as_bytes()],
-        bump
-    )]
+    #[account(mut, address = nft_info.nft_mint)]
     pub nft_mint: InterfaceAccount<'info, Mint>,
 
     #[account(
         mut,
         seeds = [
             BIDDER_ESCROW_SEEDS,
-            nft_info.library_name.as_bytes(),
+            library_name.as_bytes(),
             name.as_bytes(),
         ],
         bump
     )]
     pub nft_bidder_escrow: Account<'info, EscrowBidder>,
 
-    #[account(mut, seeds = [LIBRARY_ACCOUNT_SEEDS, nft_info.library_name.as_bytes()], bump)]
+    #[account(mut, seeds = [LIBRARY_ACCOUNT_SEEDS, library_name.as_bytes()], bump)]
     pub library_account: InterfaceAccount<'info, TokenAccount>,
 
-    /// CHECK: This is the previous bidder's escrow account (holds the funds)
+    // Everything this bidder has locked in the escrow, withdrawable once outbid
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = 8 + PendingRefund::INIT_SPACE,
+        seeds = [
+            PENDING_REFUND_SEEDS,
+            library_name.as_bytes(),
+            name.as_bytes(),
+            payer.key().as_ref(),
+        ],
+        bump
+    )]
+    pub pending_refund: Account<'info, PendingRefund>,
+
+    /// CHECK: Seller wallet, paid out when a Dutch auction settles on the bid
+    #[account(mut, address = auction_state.seller @ ErrorCode::UnauthorizedTransfer)]
+    pub seller: AccountInfo<'info>,
+
+    /// CHECK: Wallet that paid the rent for the NFT accounts at mint time
+    #[account(mut, address = nft_bidder_escrow.payer @ ErrorCode::InvalidRentPayer)]
+    pub rent_payer: AccountInfo<'info>,
+
+    #[account(
+        mut,
+        associated_token::mint = nft_mint,
+        associated_token::authority = nft_bidder_escrow,
+    )]
+    pub from_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    /// CHECK: Bidder NFT account, created and validated by the associated token program on a Dutch win
     #[account(mut)]
-    pub previous_bidder: AccountInfo<'info>,
+    pub to_token_account: UncheckedAccount<'info>,
 
-    #[account(mut, seeds = [LIBRARY_MINT_SEEDS, nft_info.library_name.as_bytes()], bump)]
+    #[account(mut, seeds = [LIBRARY_MINT_SEEDS, library_name.as_bytes()], bump)]
     pub library_mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        seeds = [LIBRARY_CONFIG_SEEDS, library_name.as_bytes()],
+        bump = library_config.bump
+    )]
+    pub library_config: Account<'info, LibraryConfig>,
+
+    #[account(
+        seeds = [
+            LIBRARY_METADATA_SEEDS,
+            metadata_program.key().as_ref(),
+            nft_mint.key().as_ref()
+        ],
+        bump,
+        seeds::program = metadata_program.key()
+    )]
+    pub nft_metadata: Box<Account<'info, MetadataAccount>>,
+
+    /// CHECK: Must match `LibraryConfig.protocol_fee_account`, only needed when a protocol fee is set
+    #[account(mut)]
+    pub protocol_fee_account: Option<UncheckedAccount<'info>>,
+
+    // Only for gated auctions, see `AuctionState.gate`
+    pub gate_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    pub gate_metadata: Option<Box<Account<'info, MetadataAccount>>>,
+
+    // Only for auctions priced in an SPL mint, see `NftInfo.payment_mint`
+    pub payment_mint: Option<InterfaceAccount<'info, Mint>>,
+
+    #[account(mut)]
+    pub escrow_payment_account: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(mut)]
+    pub payer_payment_account: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(mut)]
+    pub seller_payment_account: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(mut)]
+    pub protocol_fee_payment_account: Option<InterfaceAccount<'info, TokenAccount>>,
+
     pub system_program: Program<'info, System>,
     pub token_program: Interface<'info, TokenInterface>,
     pub metadata_program: Program<'info, Metadata>,
@@ -57,57 +125,188 @@ pub struct BidNft<'info> {
     pub rent: Sysvar<'info, Rent>,
 }
 
-pub fn _bid_nft(ctx: &mut Context<BidNft>, _name: String) -> Result<()> {
-    let auction_state: &mut Account<'_, AuctionState> = &mut ctx.accounts.auction_state;
-    let nft_info: &mut Account<'_, NftInfo> = &mut ctx.accounts.nft_info;
-
-    _common_bidding_logic(
-        auction_state,
-        nft_info,
-        &mut ctx.accounts.nft_bidder_escrow,
-        &mut ctx.accounts.previous_bidder,
-        &ctx.accounts.system_program,
-        &mut ctx.accounts.payer
-    )
+pub fn _bid_nft<'info>(
+    ctx: &mut Context<'_, '_, 'info, 'info, BidNft<'info>>,
+    _name: String,
+    _library_name: String,
+    bid_amount: u64,
+    merkle_proof: Option<Vec<[u8; 32]>>
+) -> Result<()> {
+    // The seller holds the current_bidder slot until the first bid, see `_common_bidding_logic`
+    require_keys_neq!(
+        ctx.accounts.payer.key(),
+        ctx.accounts.auction_state.seller,
+        ErrorCode::SellerCannotBid
+    );
+
+    // Sealed auctions go through `commit_bid` and `reveal_bid`
+    require!(
+        !matches!(ctx.accounts.auction_state.auction_type, AuctionType::Sealed { .. }),
+        ErrorCode::SealedBidsOnly
+    );
+
+    _check_gate(&ctx.accounts.auction_state.gate, &ctx.accounts.payer.key(), GateProof {
+        token_account: ctx.accounts.gate_token_account.as_ref(),
+        metadata: ctx.accounts.gate_metadata.as_deref().map(|metadata| &**metadata),
+        merkle_proof,
+    })?;
+
+    ctx.accounts.pending_refund.open(
+        ctx.accounts.payer.key(),
+        ctx.accounts.nft_info.payment_mint,
+        ctx.bumps.pending_refund
+    );
+
+    if let AuctionType::Dutch { .. } = ctx.accounts.auction_state.auction_type {
+        return _dutch_bidding_logic(ctx, bid_amount);
+    }
+
+    _common_bidding_logic(ctx.accounts, bid_amount)
 }
 
-pub fn _common_bidding_logic<'info>(
-    auction_state: &mut Account<'info, AuctionState>,
-    nft_info: &mut Account<'info, NftInfo>,
-    nft_bidder_escrow: &mut Account<'info, EscrowBidder>,
-    previous_bidder: &mut AccountInfo<'info>,
-    system_program: &Program<'info, System>,
-    payer: &mut Signer<'info>
+/// First bid at or above the current Dutch price wins and settles the auction on the spot
+pub fn _dutch_bidding_logic<'info>(
+    ctx: &mut Context<'_, '_, 'info, 'info, BidNft<'info>>,
+    bid_amount: u64
 ) -> Result<()> {
     let clock = Clock::get()?;
+    let accounts = &mut ctx.accounts;
 
-    let previous_bidder_key = nft_info.current_bidder;
-    let refund_amount = nft_info.current_price;
+    require!(
+        accounts.auction_state.has_started(clock.unix_timestamp),
+        ErrorCode::AuctionNotStarted
+    );
+    require!(
+        clock.unix_timestamp < accounts.auction_state.auction_end_time,
+        ErrorCode::AuctionTimeExpired
+    );
 
-    require!(clock.unix_timestamp < auction_state.auction_end_time, ErrorCode::AuctionTimeExpired);
+    let price = accounts.auction_state
+        .dutch_price(clock.unix_timestamp)
+        .ok_or(ErrorCode::AuctionNotActive)?;
 
-    let bid_amount = nft_info.current_price + nft_info.bid_step;
+    require!(bid_amount >= price, ErrorCode::BidTooLow);
 
-    transfer(
-        CpiContext::new(system_program.to_account_info(), Transfer {
-            from: payer.to_account_info(),
-            to: nft_bidder_escrow.to_account_info(),
-        }),
-        bid_amount
+    if let Some(reserve_price) = accounts.nft_info.reserve_price {
+        require!(price >= reserve_price, ErrorCode::ReservePriceNotMet);
+    }
+
+    let token_payment = _token_payment(
+        accounts.nft_info.payment_mint,
+        &accounts.nft_bidder_escrow.key(),
+        &accounts.payment_mint,
+        &accounts.escrow_payment_account,
+        &accounts.token_program
+    )?;
+
+    _lock_bid(
+        &accounts.payer.to_account_info(),
+        accounts.payer_payment_account.as_ref(),
+        &accounts.nft_bidder_escrow.to_account_info(),
+        &mut accounts.pending_refund,
+        &token_payment,
+        &accounts.system_program,
+        price
+    )?;
+
+    accounts.nft_info.current_bidder = accounts.payer.key();
+    accounts.nft_info.current_price = price;
+
+    emit!(BidPlaced {
+        nft_name: accounts.nft_info.name.clone(),
+        bidder: accounts.payer.key(),
+        timestamp: clock.unix_timestamp,
+        nft_address: accounts.nft_info.key(),
+        amount: price,
+        auction_end_time: clock.unix_timestamp,
+    });
+
+    create_idempotent(
+        CpiContext::new(accounts.associated_token_program.to_account_info(), Create {
+            payer: accounts.payer.to_account_info(),
+            associated_token: accounts.to_token_account.to_account_info(),
+            authority: accounts.payer.to_account_info(),
+            mint: accounts.nft_mint.to_account_info(),
+            system_program: accounts.system_program.to_account_info(),
+            token_program: accounts.token_program.to_account_info(),
+        })
     )?;
 
-    if previous_bidder_key != payer.key() {
-        require!(previous_bidder.key() == previous_bidder_key, ErrorCode::InvalidPreviousBidder);
-        require!(previous_bidder.owner == &system_program::ID, ErrorCode::InvalidAccountOwner);
-        require!(nft_bidder_escrow.get_lamports() >= refund_amount, ErrorCode::InsufficientFunds);
+    _common_settlement_logic(SettlementAccounts {
+        nft_info: &mut accounts.nft_info,
+        nft_bidder_escrow: &mut accounts.nft_bidder_escrow,
+        auction_state: &mut accounts.auction_state,
+        nft_mint: &accounts.nft_mint,
+        from_token_account: &accounts.from_token_account,
+        to_token_account: &accounts.to_token_account.to_account_info(),
+        recipient: &accounts.payer.to_account_info(),
+        winner_refund: Some(&mut accounts.pending_refund),
+        seller: &accounts.seller,
+        seller_payment_account: accounts.seller_payment_account.as_ref(),
+        rent_payer: &accounts.rent_payer,
+        token_program: &accounts.token_program,
+        token_payment,
+        fee_accounts: FeeAccounts {
+            library_config: &accounts.library_config,
+            nft_metadata: &accounts.nft_metadata,
+            protocol_fee_account: accounts.protocol_fee_account.as_deref(),
+            protocol_fee_payment_account: accounts.protocol_fee_payment_account.as_ref(),
+            creator_accounts: ctx.remaining_accounts,
+        },
+    })
+}
+
+pub fn _common_bidding_logic(accounts: &mut BidNft, bid_amount: u64) -> Result<()> {
+    let clock = Clock::get()?;
+    let auction_state = &mut accounts.auction_state;
+    let nft_info = &mut accounts.nft_info;
+    let payer = &accounts.payer;
+
+    require!(auction_state.has_started(clock.unix_timestamp), ErrorCode::AuctionNotStarted);
+    require!(clock.unix_timestamp < auction_state.auction_end_time, ErrorCode::AuctionTimeExpired);
+
+    // The seller holds the current_bidder slot until the first bid
+    let has_bids = nft_info.current_bidder != auction_state.seller;
+
+    if has_bids {
+        let min_bid = nft_info.current_price
+            .checked_add(nft_info.bid_step)
+            .ok_or(ErrorCode::ArithmeticOverflow)?;
 
-        **nft_bidder_escrow.to_account_info().try_borrow_mut_lamports()? -= refund_amount;
-        **previous_bidder.try_borrow_mut_lamports()? += refund_amount;
+        require!(bid_amount >= min_bid, ErrorCode::BidTooLow);
+    } else {
+        require!(bid_amount >= nft_info.starting_price, ErrorCode::BidBelowStartingPrice);
     }
 
+    let token_payment = _token_payment(
+        nft_info.payment_mint,
+        &accounts.nft_bidder_escrow.key(),
+        &accounts.payment_mint,
+        &accounts.escrow_payment_account,
+        &accounts.token_program
+    )?;
+
+    // The outbid leader keeps their funds in escrow and pulls them with `withdraw_refund`,
+    // a bidder raising an earlier bid only tops up the difference
+    _lock_bid(
+        &payer.to_account_info(),
+        accounts.payer_payment_account.as_ref(),
+        &accounts.nft_bidder_escrow.to_account_info(),
+        &mut accounts.pending_refund,
+        &token_payment,
+        &accounts.system_program,
+        bid_amount
+    )?;
+
     nft_info.current_bidder = payer.key();
-    nft_bidder_escrow.payer = payer.key();
-    nft_info.current_price = nft_info.current_price + nft_info.bid_step;
+    nft_info.current_price = bid_amount;
+
+    // Soft close, a bid inside the extension window pushes the end time back
+    if auction_state.auction_end_time - clock.unix_timestamp < auction_state.extension_window {
+        auction_state.auction_end_time = auction_state.auction_end_time
+            .checked_add(auction_state.extension_window)
+            .ok_or(ErrorCode::ArithmeticOverflow)?;
+    }
 
     emit!(BidPlaced {
         nft_name: nft_info.name.clone(),
@@ -115,6 +314,7 @@ pub fn _common_bidding_logic<'info>(
         timestamp: clock.unix_timestamp,
         nft_address: nft_info.key(),
         amount: bid_amount,
+        auction_end_time: auction_state.auction_end_time,
     });
 
     Ok(())
@@ -127,4 +327,5 @@ pub struct BidPlaced {
     pub bidder: Pubkey,
     pub amount: u64,
     pub timestamp: i64,
+    pub auction_end_time: i64,
 }
@@ -0,0 +1,225 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    metadata::{ Metadata, MetadataAccount },
+    token_interface::{ Mint, TokenAccount, TokenInterface },
+};
+
+use crate::enums::{ AuctionType, ErrorCode };
+use crate::state::{ AuctionState, LibraryConfig, NftInfo, PendingRefund };
+use crate::{ constants::*, BidPlaced, EscrowBidder, SettlementAccounts, _common_settlement_logic };
+use crate::utils::{ FeeAccounts, GateProof, _check_gate, _lock_bid, _token_payment };
+
+#[derive(Accounts)]
+#[instruction(name: String, library_name: String)]
+pub struct BuyNow<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    #[account(mut, seeds = [BUID_NFT_SEEDS, library_name.as_bytes(), name.as_bytes()], bump)]
+    pub nft_info: Account<'info, NftInfo>,
+
+    #[account(mut, seeds = [AUCTION_STATE_SEEDS, library_name.as_bytes(), name.as_bytes()], bump)]
+    pub auction_state: Account<'info, AuctionState>,
+
+    #[account(mut, address = nft_info.nft_mint)]
+    pub nft_mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        mut,
+        seeds = [
+            BIDDER_ESCROW_SEEDS,
+            library_name.as_bytes(),
+            name.as_bytes(),
+        ],
+        bump
+    )]
+    pub nft_bidder_escrow: Account<'info, EscrowBidder>,
+
+    // Everything the buyer has locked in the escrow, the buy-now price is taken from it
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = 8 + PendingRefund::INIT_SPACE,
+        seeds = [
+            PENDING_REFUND_SEEDS,
+            library_name.as_bytes(),
+            name.as_bytes(),
+            payer.key().as_ref(),
+        ],
+        bump
+    )]
+    pub pending_refund: Account<'info, PendingRefund>,
+
+    /// CHECK: Seller wallet, receives the buy-now price
+    #[account(mut, address = auction_state.seller @ ErrorCode::UnauthorizedTransfer)]
+    pub seller: AccountInfo<'info>,
+
+    /// CHECK: Wallet that paid the rent for the NFT accounts at mint time
+    #[account(mut, address = nft_bidder_escrow.payer @ ErrorCode::InvalidRentPayer)]
+    pub rent_payer: AccountInfo<'info>,
+
+    #[account(
+        mut,
+        associated_token::mint = nft_mint,
+        associated_token::authority = nft_bidder_escrow,
+    )]
+    pub from_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        init_if_needed,
+        payer = payer,
+        associated_token::mint = nft_mint,
+        associated_token::authority = payer,
+        associated_token::token_program = token_program
+    )]
+    pub to_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        seeds = [LIBRARY_CONFIG_SEEDS, library_name.as_bytes()],
+        bump = library_config.bump
+    )]
+    pub library_config: Account<'info, LibraryConfig>,
+
+    #[account(
+        seeds = [
+            LIBRARY_METADATA_SEEDS,
+            metadata_program.key().as_ref(),
+            nft_mint.key().as_ref()
+        ],
+        bump,
+        seeds::program = metadata_program.key()
+    )]
+    pub nft_metadata: Box<Account<'info, MetadataAccount>>,
+
+    /// CHECK: Must match `LibraryConfig.protocol_fee_account`, only needed when a protocol fee is set
+    #[account(mut)]
+    pub protocol_fee_account: Option<UncheckedAccount<'info>>,
+
+    // Only for gated auctions, see `AuctionState.gate`
+    pub gate_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    pub gate_metadata: Option<Box<Account<'info, MetadataAccount>>>,
+
+    // Only for auctions priced in an SPL mint, see `NftInfo.payment_mint`
+    pub payment_mint: Option<InterfaceAccount<'info, Mint>>,
+
+    #[account(mut)]
+    pub escrow_payment_account: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(mut)]
+    pub payer_payment_account: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(mut)]
+    pub seller_payment_account: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(mut)]
+    pub protocol_fee_payment_account: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+    pub metadata_program: Program<'info, Metadata>,
+    pub system_program: Program<'info, System>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+pub fn _buy_now<'info>(
+    ctx: &mut Context<'_, '_, 'info, 'info, BuyNow<'info>>,
+    _name: String,
+    _library_name: String,
+    merkle_proof: Option<Vec<[u8; 32]>>
+) -> Result<()> {
+    let clock = Clock::get()?;
+
+    require_keys_neq!(
+        ctx.accounts.payer.key(),
+        ctx.accounts.auction_state.seller,
+        ErrorCode::SellerCannotBid
+    );
+
+    _check_gate(&ctx.accounts.auction_state.gate, &ctx.accounts.payer.key(), GateProof {
+        token_account: ctx.accounts.gate_token_account.as_ref(),
+        metadata: ctx.accounts.gate_metadata.as_deref().map(|metadata| &**metadata),
+        merkle_proof,
+    })?;
+    let accounts = &mut ctx.accounts;
+
+    require!(
+        accounts.auction_state.has_started(clock.unix_timestamp),
+        ErrorCode::AuctionNotStarted
+    );
+    require!(
+        clock.unix_timestamp < accounts.auction_state.auction_end_time,
+        ErrorCode::AuctionTimeExpired
+    );
+
+    require!(
+        !matches!(accounts.auction_state.auction_type, AuctionType::Sealed { .. }),
+        ErrorCode::SealedBidsOnly
+    );
+
+    let buy_now_price = accounts.nft_info.buy_now_price.ok_or(ErrorCode::BuyNowNotAvailable)?;
+
+    // Buy-now is gone once the bidding has caught up with it
+    require!(accounts.nft_info.current_price < buy_now_price, ErrorCode::BuyNowNotAvailable);
+
+    accounts.pending_refund.open(
+        accounts.payer.key(),
+        accounts.nft_info.payment_mint,
+        ctx.bumps.pending_refund
+    );
+
+    let token_payment = _token_payment(
+        accounts.nft_info.payment_mint,
+        &accounts.nft_bidder_escrow.key(),
+        &accounts.payment_mint,
+        &accounts.escrow_payment_account,
+        &accounts.token_program
+    )?;
+
+    // The outbid leader pulls their bid back with `withdraw_refund`
+    _lock_bid(
+        &accounts.payer.to_account_info(),
+        accounts.payer_payment_account.as_ref(),
+        &accounts.nft_bidder_escrow.to_account_info(),
+        &mut accounts.pending_refund,
+        &token_payment,
+        &accounts.system_program,
+        buy_now_price
+    )?;
+
+    accounts.nft_info.current_bidder = accounts.payer.key();
+    accounts.nft_info.current_price = buy_now_price;
+
+    emit!(BidPlaced {
+        nft_name: accounts.nft_info.name.clone(),
+        bidder: accounts.payer.key(),
+        timestamp: clock.unix_timestamp,
+        nft_address: accounts.nft_info.key(),
+        amount: buy_now_price,
+        auction_end_time: clock.unix_timestamp,
+    });
+
+    _common_settlement_logic(SettlementAccounts {
+        nft_info: &mut accounts.nft_info,
+        nft_bidder_escrow: &mut accounts.nft_bidder_escrow,
+        auction_state: &mut accounts.auction_state,
+        nft_mint: &accounts.nft_mint,
+        from_token_account: &accounts.from_token_account,
+        to_token_account: &accounts.to_token_account.to_account_info(),
+        recipient: &accounts.payer.to_account_info(),
+        winner_refund: Some(&mut accounts.pending_refund),
+        seller: &accounts.seller,
+        seller_payment_account: accounts.seller_payment_account.as_ref(),
+        rent_payer: &accounts.rent_payer,
+        token_program: &accounts.token_program,
+        token_payment,
+        fee_accounts: FeeAccounts {
+            library_config: &accounts.library_config,
+            nft_metadata: &accounts.nft_metadata,
+            protocol_fee_account: accounts.protocol_fee_account.as_deref(),
+            protocol_fee_payment_account: accounts.protocol_fee_payment_account.as_ref(),
+            creator_accounts: ctx.remaining_accounts,
+        },
+    })
+}
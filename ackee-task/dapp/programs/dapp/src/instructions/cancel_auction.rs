@@ -0,0 +1,74 @@
+use anchor_lang::prelude::*;
+
+use crate::enums::ErrorCode;
+use crate::state::{ AuctionState, NftInfo };
+use crate::constants::*;
+
+/// Aborts a running auction. The NFT stays in the escrow, so the seller can open a new
+/// auction on it with `start_auction`, or let that one run out and `withdraw_unsold`.
+#[derive(Accounts)]
+#[instruction(name: String, library_name: String)]
+pub struct CancelAuction<'info> {
+    #[account(mut)]
+    pub seller: Signer<'info>,
+
+    #[account(mut, seeds = [BUID_NFT_SEEDS, library_name.as_bytes(), name.as_bytes()], bump)]
+    pub nft_info: Account<'info, NftInfo>,
+
+    #[account(
+        mut,
+        close = seller,
+        seeds = [AUCTION_STATE_SEEDS, library_name.as_bytes(), name.as_bytes()],
+        bump,
+        constraint = auction_state.seller == seller.key() @ ErrorCode::UnauthorizedCancel
+    )]
+    pub auction_state: Account<'info, AuctionState>,
+}
+
+pub fn _cancel_auction(
+    ctx: &mut Context<CancelAuction>,
+    name: String,
+    _library_name: String
+) -> Result<()> {
+    let clock = Clock::get()?;
+    let seller = ctx.accounts.seller.key();
+    let nft_info = &mut ctx.accounts.nft_info;
+
+    require!(clock.unix_timestamp < ctx.accounts.auction_state.auction_end_time, ErrorCode::AuctionEnded);
+
+    let leading_bidder = nft_info.current_bidder;
+    let mut leading_bid = 0;
+
+    // Nobody has bid while the seller is still the current bidder, otherwise the
+    // leading bid becomes withdrawable through `withdraw_refund`
+    if leading_bidder != seller {
+        leading_bid = nft_info.current_price;
+
+        nft_info.reset_bids(seller);
+    }
+
+    // Sealed commitments made for this auction cannot be revealed in the next one
+    nft_info.next_round()?;
+
+    emit!(AuctionCancelled {
+        nft_name: name,
+        nft_address: nft_info.key(),
+        seller,
+        leading_bidder,
+        leading_bid,
+        timestamp: clock.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+/// `leading_bid` is not paid out, `leading_bidder` pulls it with `withdraw_refund`
+#[event]
+pub struct AuctionCancelled {
+    pub nft_name: String,
+    pub nft_address: Pubkey,
+    pub seller: Pubkey,
+    pub leading_bidder: Pubkey,
+    pub leading_bid: u64,
+    pub timestamp: i64,
+}
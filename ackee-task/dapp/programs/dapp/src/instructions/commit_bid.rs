@@ -0,0 +1,157 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{ metadata::MetadataAccount, token_interface::{ Mint, TokenAccount, TokenInterface } };
+
+use crate::enums::{ AuctionType, ErrorCode };
+use crate::state::{ AuctionState, EscrowBidder, NftInfo, PendingRefund, SealedBid };
+use crate::constants::*;
+use crate::utils::{ GateProof, _check_gate, _lock_bid, _token_payment };
+
+#[derive(Accounts)]
+#[instruction(name: String, library_name: String)]
+pub struct CommitBid<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    #[account(seeds = [BUID_NFT_SEEDS, library_name.as_bytes(), name.as_bytes()], bump)]
+    pub nft_info: Account<'info, NftInfo>,
+
+    #[account(seeds = [AUCTION_STATE_SEEDS, library_name.as_bytes(), name.as_bytes()], bump)]
+    pub auction_state: Account<'info, AuctionState>,
+
+    #[account(
+        mut,
+        seeds = [
+            BIDDER_ESCROW_SEEDS,
+            library_name.as_bytes(),
+            name.as_bytes(),
+        ],
+        bump
+    )]
+    pub nft_bidder_escrow: Account<'info, EscrowBidder>,
+
+    // The deposit, it has to cover the amount revealed later
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = 8 + PendingRefund::INIT_SPACE,
+        seeds = [
+            PENDING_REFUND_SEEDS,
+            library_name.as_bytes(),
+            name.as_bytes(),
+            payer.key().as_ref(),
+        ],
+        bump
+    )]
+    pub pending_refund: Account<'info, PendingRefund>,
+
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = 8 + SealedBid::INIT_SPACE,
+        seeds = [
+            SEALED_BID_SEEDS,
+            library_name.as_bytes(),
+            name.as_bytes(),
+            payer.key().as_ref(),
+        ],
+        bump
+    )]
+    pub sealed_bid: Account<'info, SealedBid>,
+
+    // Only for gated auctions, see `AuctionState.gate`
+    pub gate_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    pub gate_metadata: Option<Box<Account<'info, MetadataAccount>>>,
+
+    // Only for auctions priced in an SPL mint, see `NftInfo.payment_mint`
+    pub payment_mint: Option<InterfaceAccount<'info, Mint>>,
+
+    #[account(mut)]
+    pub escrow_payment_account: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(mut)]
+    pub payer_payment_account: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+}
+
+pub fn _commit_bid(
+    ctx: &mut Context<CommitBid>,
+    name: String,
+    _library_name: String,
+    commitment: [u8; 32],
+    deposit: u64,
+    merkle_proof: Option<Vec<[u8; 32]>>
+) -> Result<()> {
+    let clock = Clock::get()?;
+    let accounts = &mut ctx.accounts;
+
+    require_keys_neq!(accounts.payer.key(), accounts.auction_state.seller, ErrorCode::SellerCannotBid);
+    require!(
+        matches!(accounts.auction_state.auction_type, AuctionType::Sealed { .. }),
+        ErrorCode::NotSealedAuction
+    );
+    require!(
+        accounts.auction_state.has_started(clock.unix_timestamp),
+        ErrorCode::AuctionNotStarted
+    );
+    require!(
+        clock.unix_timestamp < accounts.auction_state.auction_end_time,
+        ErrorCode::AuctionTimeExpired
+    );
+
+    _check_gate(&accounts.auction_state.gate, &accounts.payer.key(), GateProof {
+        token_account: accounts.gate_token_account.as_ref(),
+        metadata: accounts.gate_metadata.as_deref().map(|metadata| &**metadata),
+        merkle_proof,
+    })?;
+
+    accounts.pending_refund.open(
+        accounts.payer.key(),
+        accounts.nft_info.payment_mint,
+        ctx.bumps.pending_refund
+    );
+
+    let token_payment = _token_payment(
+        accounts.nft_info.payment_mint,
+        &accounts.nft_bidder_escrow.key(),
+        &accounts.payment_mint,
+        &accounts.escrow_payment_account,
+        &accounts.token_program
+    )?;
+
+    // Deposits larger than the bid hide it, a new commitment only tops the deposit up
+    _lock_bid(
+        &accounts.payer.to_account_info(),
+        accounts.payer_payment_account.as_ref(),
+        &accounts.nft_bidder_escrow.to_account_info(),
+        &mut accounts.pending_refund,
+        &token_payment,
+        &accounts.system_program,
+        deposit
+    )?;
+
+    let sealed_bid = &mut accounts.sealed_bid;
+    sealed_bid.bidder = accounts.payer.key();
+    sealed_bid.commitment = commitment;
+    sealed_bid.bump = ctx.bumps.sealed_bid;
+    sealed_bid.round = accounts.nft_info.round;
+
+    emit!(BidCommitted {
+        nft_name: name,
+        bidder: accounts.payer.key(),
+        deposit: accounts.pending_refund.amount,
+        timestamp: clock.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+#[event]
+pub struct BidCommitted {
+    pub nft_name: String,
+    pub bidder: Pubkey,
+    pub deposit: u64,
+    pub timestamp: i64,
+}
@@ -7,12 +7,13 @@ use anchor_spl::{
         Metadata,
         create_master_edition_v3,
         create_metadata_accounts_v3,
-        mpl_token_metadata::{ types::{ CollectionDetails, Creator, DataV2 } },
+        mpl_token_metadata::{ types::{ CollectionDetails, DataV2 } },
         sign_metadata,
     },
     token_interface::{ Mint, MintTo, TokenAccount, TokenInterface, mint_to },
 };
-use crate::{ constants::* };
+use crate::{ constants::*, Library, LibraryConfig };
+use crate::utils::{ NftCreator, _metadata_creators, _validate_metadata };
 
 #[derive(Accounts)]
 #[instruction(name: String)]
@@ -41,6 +42,24 @@ pub struct InitLibrary<'info> {
     )]
     pub library_account: InterfaceAccount<'info, TokenAccount>,
 
+    #[account(
+        init,
+        payer = signer,
+        space = 8 + Library::INIT_SPACE,
+        seeds = [LIBRARY_SEEDS, name.as_bytes()],
+        bump
+    )]
+    pub library: Account<'info, Library>,
+
+    #[account(
+        init,
+        payer = signer,
+        space = 8 + LibraryConfig::INIT_SPACE,
+        seeds = [LIBRARY_CONFIG_SEEDS, name.as_bytes()],
+        bump
+    )]
+    pub library_config: Account<'info, LibraryConfig>,
+
     #[account(
         mut,
         seeds = [
@@ -75,9 +94,63 @@ pub struct InitLibrary<'info> {
     pub rent: Sysvar<'info, Rent>,
 }
 
-pub fn _init_library(ctx: &Context<InitLibrary>, name: String) -> Result<()> {
+#[allow(clippy::too_many_arguments)]
+pub fn _init_library(
+    ctx: &mut Context<InitLibrary>,
+    name: String,
+    description: String,
+    url: String,
+    icon: String,
+    uri: String,
+    symbol: Option<String>,
+    creators: Option<Vec<NftCreator>>,
+    seller_fee_basis_points: u16,
+    protocol_fee_basis_points: u16,
+    protocol_fee_account: Option<Pubkey>
+) -> Result<()> {
     msg!("Initializing library");
 
+    let symbol = _validate_metadata(&name, symbol, &uri)?;
+    let creators = _metadata_creators(
+        creators,
+        ctx.accounts.library_mint.key(),
+        Some(ctx.accounts.library_mint.key())
+    )?;
+    let library_is_creator = creators
+        .iter()
+        .any(|creator| creator.address == ctx.accounts.library_mint.key());
+    let signer_is_creator = creators
+        .iter()
+        .any(|creator| creator.address == ctx.accounts.signer.key());
+
+    Library::validate_field(&description)?;
+    Library::validate_field(&url)?;
+    Library::validate_field(&icon)?;
+
+    LibraryConfig::validate_fees(
+        seller_fee_basis_points,
+        protocol_fee_basis_points,
+        protocol_fee_account
+    )?;
+
+    *ctx.accounts.library_config = LibraryConfig {
+        seller_fee_basis_points,
+        protocol_fee_basis_points,
+        protocol_fee_account,
+        bump: ctx.bumps.library_config,
+    };
+
+    // The wallet creating the library becomes its admin authority
+    *ctx.accounts.library = Library {
+        authority: ctx.accounts.signer.key(),
+        delegates: Vec::new(),
+        name: name.to_owned(),
+        description,
+        url,
+        icon,
+        bump: ctx.bumps.library,
+    };
+
     let signer_seeds: &[&[&[u8]]] = &[
         &[LIBRARY_MINT_SEEDS, name.as_bytes(), &[ctx.bumps.library_mint]],
     ];
@@ -114,17 +187,11 @@ pub fn _init_library(ctx: &Context<InitLibrary>, name: String) -> Result<()> {
             signer_seeds
         ),
         DataV2 {
-            name: NAME.to_string(),
-            symbol: SYMBOL.to_string(),
-            uri: COLLECTION_URL.to_string(),
-            seller_fee_basis_points: 0,
-            creators: Some(
-                vec![Creator {
-                    address: ctx.accounts.library_mint.key(),
-                    verified: true,
-                    share: 100,
-                }]
-            ),
+            name: name.to_owned(),
+            symbol,
+            uri,
+            seller_fee_basis_points,
+            creators: Some(creators),
             collection: None,
             uses: None,
         },
@@ -156,16 +223,27 @@ pub fn _init_library(ctx: &Context<InitLibrary>, name: String) -> Result<()> {
 
     msg!("{{!!!!Library master edition:{}!!!!}}", ctx.accounts.master_edition.key());
 
-    sign_metadata(
-        CpiContext::new_with_signer(
-            ctx.accounts.metadata_program.to_account_info(),
-            SignMetadata {
-                creator: ctx.accounts.library_mint.to_account_info(),
+    if library_is_creator {
+        sign_metadata(
+            CpiContext::new_with_signer(
+                ctx.accounts.metadata_program.to_account_info(),
+                SignMetadata {
+                    creator: ctx.accounts.library_mint.to_account_info(),
+                    metadata: ctx.accounts.library_metadata.to_account_info(),
+                },
+                signer_seeds
+            )
+        )?;
+    }
+
+    if signer_is_creator {
+        sign_metadata(
+            CpiContext::new(ctx.accounts.metadata_program.to_account_info(), SignMetadata {
+                creator: ctx.accounts.signer.to_account_info(),
                 metadata: ctx.accounts.library_metadata.to_account_info(),
-            },
-            signer_seeds
-        )
-    )?;
+            })
+        )?;
+    }
     let clock = Clock::get()?;
 
     emit!(InitLibraryEvent {
@@ -0,0 +1,102 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::Mint;
+
+use crate::enums::ErrorCode;
+use crate::{ constants::*, Library, LibraryConfig };
+
+/// Creates the `Library` and `LibraryConfig` PDAs for a library initialized before they existed.
+///
+/// Legacy libraries have no on-chain owner (every authority is the `library_mint` PDA), so only
+/// the program upgrade authority may claim one and hand it to `authority`. Description, url and
+/// icon start empty and can be filled in afterwards with `update_library`. The collection
+/// metadata is left untouched, its seller fee stays whatever `init_library` wrote at the time.
+#[derive(Accounts)]
+#[instruction(name: String)]
+pub struct MigrateLibrary<'info> {
+    #[account(mut)]
+    pub signer: Signer<'info>,
+
+    #[account(
+        seeds = [crate::ID.as_ref()],
+        bump,
+        // `ProgramData` accounts are owned by the upgradeable loader, which also derives their address
+        seeds::program = ProgramData::owner(),
+        constraint = program_data.upgrade_authority_address == Some(signer.key()) @ ErrorCode::UnauthorizedMigration
+    )]
+    pub program_data: Account<'info, ProgramData>,
+
+    // Only libraries that were actually initialized can be migrated
+    #[account(seeds = [LIBRARY_MINT_SEEDS, name.as_bytes()], bump)]
+    pub library_mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        init,
+        payer = signer,
+        space = 8 + Library::INIT_SPACE,
+        seeds = [LIBRARY_SEEDS, name.as_bytes()],
+        bump
+    )]
+    pub library: Account<'info, Library>,
+
+    #[account(
+        init,
+        payer = signer,
+        space = 8 + LibraryConfig::INIT_SPACE,
+        seeds = [LIBRARY_CONFIG_SEEDS, name.as_bytes()],
+        bump
+    )]
+    pub library_config: Account<'info, LibraryConfig>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn _migrate_library(
+    ctx: &mut Context<MigrateLibrary>,
+    name: String,
+    authority: Pubkey,
+    seller_fee_basis_points: u16,
+    protocol_fee_basis_points: u16,
+    protocol_fee_account: Option<Pubkey>
+) -> Result<()> {
+    LibraryConfig::validate_fees(
+        seller_fee_basis_points,
+        protocol_fee_basis_points,
+        protocol_fee_account
+    )?;
+
+    *ctx.accounts.library_config = LibraryConfig {
+        seller_fee_basis_points,
+        protocol_fee_basis_points,
+        protocol_fee_account,
+        bump: ctx.bumps.library_config,
+    };
+
+    *ctx.accounts.library = Library {
+        authority,
+        delegates: Vec::new(),
+        name: name.to_owned(),
+        description: String::new(),
+        url: String::new(),
+        icon: String::new(),
+        bump: ctx.bumps.library,
+    };
+
+    let clock = Clock::get()?;
+
+    emit!(LibraryMigrated {
+        name,
+        library_address: ctx.accounts.library.key(),
+        authority,
+        timestamp: clock.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+#[event]
+pub struct LibraryMigrated {
+    pub name: String,
+    pub library_address: Pubkey,
+    pub authority: Pubkey,
+    pub timestamp: i64,
+}
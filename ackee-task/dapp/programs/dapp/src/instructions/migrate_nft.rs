@@ -0,0 +1,261 @@
+use anchor_lang::{ prelude::*, system_program };
+use anchor_lang::system_program::{ transfer, Transfer };
+use anchor_spl::token_interface::Mint;
+
+use crate::enums::{ AuctionType, ErrorCode };
+use crate::state::{ AuctionState, LegacyAuctionState, LegacyNftInfo, NftInfo, PendingRefund };
+use crate::{ constants::*, EscrowBidder };
+
+/// Moves an `NftInfo` minted before library scoping to its library-scoped PDA, the library
+/// itself must have been brought over with `migrate_library` first
+///
+/// A legacy auction that is still open is carried over as an English auction with the same end
+/// time, signed by its seller. The leading bidder gets a `PendingRefund` for their bid, the seller
+/// tops the escrow up to it since legacy bids paid the starting price out to the minter.
+#[derive(Accounts)]
+#[instruction(name: String, library_name: String)]
+pub struct MigrateNft<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// CHECK: Pre-migration `NftInfo`, decoded by hand as `LegacyNftInfo` and closed below
+    #[account(mut, seeds = [BUID_NFT_SEEDS, name.as_bytes()], bump, owner = crate::ID)]
+    pub legacy_nft_info: UncheckedAccount<'info>,
+
+    /// CHECK: Pre-migration auction PDA, decoded by hand as `LegacyAuctionState` and closed below
+    /// when an auction is running
+    #[account(mut, seeds = [AUCTION_STATE_SEEDS, name.as_bytes()], bump)]
+    pub legacy_auction_state: UncheckedAccount<'info>,
+
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + NftInfo::INIT_SPACE,
+        seeds = [BUID_NFT_SEEDS, library_name.as_bytes(), name.as_bytes()],
+        bump
+    )]
+    pub nft_info: Account<'info, NftInfo>,
+
+    // Only when a legacy auction is running, it continues here
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + AuctionState::INIT_SPACE,
+        seeds = [AUCTION_STATE_SEEDS, library_name.as_bytes(), name.as_bytes()],
+        bump
+    )]
+    pub auction_state: Option<Account<'info, AuctionState>>,
+
+    /// CHECK: Leading bidder of the legacy auction, must match `LegacyNftInfo.current_bidder`
+    pub legacy_bidder: Option<UncheckedAccount<'info>>,
+
+    // Only when the legacy auction has a bid, it holds the leading bid from now on
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + PendingRefund::INIT_SPACE,
+        seeds = [
+            PENDING_REFUND_SEEDS,
+            library_name.as_bytes(),
+            name.as_bytes(),
+            legacy_bidder.as_ref().ok_or(ErrorCode::InvalidPreviousBidder)?.key().as_ref(),
+        ],
+        bump
+    )]
+    pub bidder_refund: Option<Account<'info, PendingRefund>>,
+
+    // Legacy mints keep their unscoped address, `NftInfo.nft_mint` points at it from now on
+    #[account(seeds = [name.as_bytes()], bump)]
+    pub nft_mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        mut,
+        seeds = [BIDDER_ESCROW_SEEDS, library_name.as_bytes(), name.as_bytes()],
+        bump = nft_bidder_escrow.bump
+    )]
+    pub nft_bidder_escrow: Account<'info, EscrowBidder>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn _migrate_nft(ctx: &mut Context<MigrateNft>, name: String, library_name: String) -> Result<()> {
+    let clock = Clock::get()?;
+
+    let legacy_nft_info = {
+        let data = ctx.accounts.legacy_nft_info.try_borrow_data()?;
+
+        require!(
+            data.len() > 8 && data[..8] == *NftInfo::DISCRIMINATOR,
+            ErrorCode::InvalidLegacyAccount
+        );
+
+        LegacyNftInfo::deserialize(&mut &data[8..])?
+    };
+
+    require!(
+        legacy_nft_info.name == name && legacy_nft_info.library_name == library_name,
+        ErrorCode::InvalidLegacyAccount
+    );
+
+    let legacy_auction_state = _read_legacy_auction_state(&ctx.accounts.legacy_auction_state)?;
+    let payer = ctx.accounts.payer.key();
+
+    // Legacy bids overwrote the escrow payer, the seller of a running auction takes it over
+    match &legacy_auction_state {
+        Some(legacy_auction_state) => {
+            require_keys_eq!(payer, legacy_auction_state.seller, ErrorCode::UnauthorizedTransfer);
+        }
+        None => {
+            require_keys_eq!(payer, ctx.accounts.nft_bidder_escrow.payer, ErrorCode::InvalidRentPayer);
+            require!(ctx.accounts.auction_state.is_none(), ErrorCode::InvalidLegacyAccount);
+        }
+    }
+
+    *ctx.accounts.nft_info = NftInfo {
+        current_price: legacy_nft_info.current_price,
+        starting_price: legacy_nft_info.starting_price,
+        current_bidder: legacy_nft_info.current_bidder,
+        bid_step: legacy_nft_info.bid_step,
+        // Legacy lots were SOL-only open auctions without reserve or buy-now
+        reserve_price: None,
+        buy_now_price: None,
+        payment_mint: None,
+        bump: ctx.bumps.nft_info,
+        library_name: legacy_nft_info.library_name,
+        name: legacy_nft_info.name,
+        nft_mint: ctx.accounts.nft_mint.key(),
+        round: 0,
+    };
+
+    let mut carries_bid = false;
+
+    if let Some(legacy_auction_state) = legacy_auction_state {
+        let auction_state = ctx.accounts.auction_state
+            .as_mut()
+            .ok_or(ErrorCode::InvalidLegacyAccount)?;
+
+        auction_state.set_inner(AuctionState {
+            nft_mint: ctx.accounts.nft_mint.key(),
+            seller: payer,
+            auction_start_time: clock.unix_timestamp.min(legacy_auction_state.auction_end_time),
+            auction_end_time: legacy_auction_state.auction_end_time,
+            extension_window: 0,
+            auction_type: AuctionType::English,
+            bump: ctx.bumps.auction_state.ok_or(ErrorCode::InvalidLegacyAccount)?,
+            gate: None,
+            runner_up_price: 0,
+        });
+
+        if legacy_nft_info.current_bidder != payer {
+            carries_bid = true;
+
+            _carry_over_leading_bid(ctx, legacy_nft_info.current_bidder, legacy_nft_info.current_price)?;
+        }
+
+        ctx.accounts.nft_bidder_escrow.payer = payer;
+
+        _close_legacy_account(
+            &ctx.accounts.legacy_auction_state.to_account_info(),
+            &ctx.accounts.payer.to_account_info()
+        )?;
+    }
+
+    require!(
+        carries_bid || ctx.accounts.bidder_refund.is_none(),
+        ErrorCode::InvalidPendingRefund
+    );
+
+    // Same as an Anchor `close`, the rent goes back to whoever paid it at mint
+    _close_legacy_account(
+        &ctx.accounts.legacy_nft_info.to_account_info(),
+        &ctx.accounts.payer.to_account_info()
+    )?;
+
+    emit!(NftMigrated {
+        name,
+        library_name,
+        legacy_address: ctx.accounts.legacy_nft_info.key(),
+        nft_address: ctx.accounts.nft_info.key(),
+        timestamp: clock.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+/// `None` while no legacy auction is running on the lot
+fn _read_legacy_auction_state(account: &AccountInfo) -> Result<Option<LegacyAuctionState>> {
+    if account.data_is_empty() {
+        return Ok(None);
+    }
+
+    let data = account.try_borrow_data()?;
+
+    require_keys_eq!(*account.owner, crate::ID, ErrorCode::InvalidAccountOwner);
+    require!(
+        data.len() > 8 && data[..8] == *AuctionState::DISCRIMINATOR,
+        ErrorCode::InvalidLegacyAccount
+    );
+
+    Ok(Some(LegacyAuctionState::deserialize(&mut &data[8..])?))
+}
+
+/// Records the legacy leading bid as the bidder's `PendingRefund` and makes sure the escrow
+/// holds all of it
+fn _carry_over_leading_bid(ctx: &mut Context<MigrateNft>, bidder: Pubkey, amount: u64) -> Result<()> {
+    let legacy_bidder = ctx.accounts.legacy_bidder
+        .as_ref()
+        .ok_or(ErrorCode::InvalidPreviousBidder)?;
+
+    require_keys_eq!(legacy_bidder.key(), bidder, ErrorCode::InvalidPreviousBidder);
+
+    let bidder_refund = ctx.accounts.bidder_refund
+        .as_mut()
+        .ok_or(ErrorCode::InvalidPendingRefund)?;
+
+    bidder_refund.open(
+        bidder,
+        None,
+        ctx.bumps.bidder_refund.ok_or(ErrorCode::InvalidPendingRefund)?
+    );
+    bidder_refund.amount = amount;
+
+    let escrow = ctx.accounts.nft_bidder_escrow.to_account_info();
+    let backing = Rent::get()?
+        .minimum_balance(escrow.data_len())
+        .checked_add(amount)
+        .ok_or(ErrorCode::ArithmeticOverflow)?;
+    let shortfall = backing.saturating_sub(escrow.lamports());
+
+    if shortfall > 0 {
+        transfer(
+            CpiContext::new(ctx.accounts.system_program.to_account_info(), Transfer {
+                from: ctx.accounts.payer.to_account_info(),
+                to: escrow,
+            }),
+            shortfall
+        )?;
+    }
+
+    Ok(())
+}
+
+fn _close_legacy_account<'info>(
+    account: &AccountInfo<'info>,
+    destination: &AccountInfo<'info>
+) -> Result<()> {
+    **destination.try_borrow_mut_lamports()? += account.lamports();
+    **account.try_borrow_mut_lamports()? = 0;
+    account.assign(&system_program::ID);
+    account.resize(0)?;
+
+    Ok(())
+}
+
+#[event]
+pub struct NftMigrated {
+    pub name: String,
+    pub library_name: String,
+    pub legacy_address: Pubkey,
+    pub nft_address: Pubkey,
+    pub timestamp: i64,
+}
@@ -8,14 +8,18 @@ use anchor_spl::{
         create_master_edition_v3,
         create_metadata_accounts_v3,
         set_and_verify_sized_collection_item,
+        sign_metadata,
+        SignMetadata,
         mpl_token_metadata::{ types::{ DataV2 } },
     },
-    associated_token::AssociatedToken,
+    associated_token::{ AssociatedToken, create_idempotent, Create },
     token_interface::{ Mint, MintTo, TokenAccount, TokenInterface, mint_to },
 };
 
-use crate::{ EscrowBidder, constants::* };
+use crate::{ EscrowBidder, Library, LibraryConfig, constants::* };
+use crate::enums::ErrorCode;
 use crate::state::nft_info::NftInfo;
+use crate::utils::{ NftCreator, _metadata_creators, _validate_metadata };
 
 #[derive(Accounts)]
 #[instruction(name: String, price: u64, library_name: String)]
@@ -27,7 +31,7 @@ pub struct MintNft<'info> {
         init,
         payer = payer,
         space = 8 + NftInfo::INIT_SPACE,
-        seeds = [BUID_NFT_SEEDS, name.as_bytes()],
+        seeds = [BUID_NFT_SEEDS, library_name.as_bytes(), name.as_bytes()],
         bump
     )]
     pub nft_info: Account<'info, NftInfo>,
@@ -35,7 +39,7 @@ pub struct MintNft<'info> {
     #[account(
         init,
         payer = payer,
-        seeds = [name.as_bytes()],
+        seeds = [NFT_MINT_SEEDS, library_name.as_bytes(), name.as_bytes()],
         mint::decimals = 0,
         mint::authority = library_mint,
         mint::freeze_authority = library_mint,
@@ -62,6 +66,19 @@ pub struct MintNft<'info> {
     /// CHECK: This account is validated by the Metaplex Token Metadata program using PDA seeds
     pub library_metadata: UncheckedAccount<'info>,
 
+    #[account(
+        seeds = [LIBRARY_SEEDS, library_name.as_bytes()],
+        bump = library.bump,
+        constraint = library.can_mint(&payer.key()) @ ErrorCode::UnauthorizedLibraryAuthority
+    )]
+    pub library: Account<'info, Library>,
+
+    #[account(
+        seeds = [LIBRARY_CONFIG_SEEDS, library_name.as_bytes()],
+        bump = library_config.bump
+    )]
+    pub library_config: Account<'info, LibraryConfig>,
+
     #[account(
         init,
         space = 8 + EscrowBidder::INIT_SPACE,
@@ -120,6 +137,13 @@ pub struct MintNft<'info> {
     )]
     pub associated_nft: InterfaceAccount<'info, TokenAccount>,
 
+    // Set both to price the auction in an SPL mint instead of lamports
+    pub payment_mint: Option<InterfaceAccount<'info, Mint>>,
+
+    /// CHECK: Escrow payment token account, created and validated by the associated token program
+    #[account(mut)]
+    pub escrow_payment_account: Option<UncheckedAccount<'info>>,
+
     pub system_program: Program<'info, System>,
     pub token_program: Interface<'info, TokenInterface>,
     pub metadata_program: Program<'info, Metadata>,
@@ -127,13 +151,32 @@ pub struct MintNft<'info> {
     pub rent: Sysvar<'info, Rent>,
 }
 
+#[allow(clippy::too_many_arguments)]
 pub fn _mint_nft(
     ctx: &mut Context<MintNft>,
     name: String,
     price: u64,
     library_name: String,
-    bid_step: u64
+    bid_step: u64,
+    reserve_price: Option<u64>,
+    buy_now_price: Option<u64>,
+    uri: String,
+    symbol: Option<String>,
+    creators: Option<Vec<NftCreator>>
 ) -> Result<()> {
+    let item = MintNftItem {
+        name,
+        price,
+        bid_step,
+        reserve_price,
+        buy_now_price,
+        uri,
+        symbol,
+        creators,
+    };
+
+    item.validate()?;
+
     let signer_seeds: &[&[&[u8]]] = &[
         &[LIBRARY_MINT_SEEDS, library_name.as_bytes(), &[ctx.bumps.library_mint]],
     ];
@@ -143,23 +186,145 @@ pub fn _mint_nft(
         bump: ctx.bumps.nft_bidder_escrow,
     };
 
-    *ctx.accounts.nft_info = NftInfo {
-        name: name.to_owned(),
-        current_bidder: ctx.accounts.payer.key(),
-        bid_step,
-        current_price: price,
-        starting_price: price,
-        library_name: library_name.to_owned(),
-        bump: ctx.bumps.nft_info,
-    };
+    *ctx.accounts.nft_info = item.nft_info(
+        &library_name,
+        ctx.accounts.payer.key(),
+        ctx.accounts.nft_mint.key(),
+        ctx.accounts.payment_mint.as_ref().map(|payment_mint| payment_mint.key()),
+        ctx.bumps.nft_info
+    );
+
+    match (&ctx.accounts.payment_mint, &ctx.accounts.escrow_payment_account) {
+        (Some(payment_mint), Some(escrow_payment_account)) => {
+            create_idempotent(
+                CpiContext::new(ctx.accounts.associated_token_program.to_account_info(), Create {
+                    payer: ctx.accounts.payer.to_account_info(),
+                    associated_token: escrow_payment_account.to_account_info(),
+                    authority: ctx.accounts.nft_bidder_escrow.to_account_info(),
+                    mint: payment_mint.to_account_info(),
+                    system_program: ctx.accounts.system_program.to_account_info(),
+                    token_program: ctx.accounts.token_program.to_account_info(),
+                })
+            )?;
+        }
+        (None, None) => {}
+        _ => {
+            return err!(ErrorCode::InvalidPaymentAccount);
+        }
+    }
+
+    _mint_nft_item(
+        &(NftMintAccounts {
+            payer: &ctx.accounts.payer.to_account_info(),
+            nft_mint: &ctx.accounts.nft_mint.to_account_info(),
+            associated_nft: &ctx.accounts.associated_nft.to_account_info(),
+            nft_metadata: &ctx.accounts.nft_metadata.to_account_info(),
+            nft_master_edition: &ctx.accounts.nft_master_edition.to_account_info(),
+            library_account: &ctx.accounts.library_account.to_account_info(),
+            library_mint: &ctx.accounts.library_mint.to_account_info(),
+            library_metadata: &ctx.accounts.library_metadata.to_account_info(),
+            library_master_edition: &ctx.accounts.library_master_edition.to_account_info(),
+            seller_fee_basis_points: ctx.accounts.library_config.seller_fee_basis_points,
+            token_program: &ctx.accounts.token_program.to_account_info(),
+            metadata_program: &ctx.accounts.metadata_program.to_account_info(),
+            system_program: &ctx.accounts.system_program.to_account_info(),
+            rent: &ctx.accounts.rent.to_account_info(),
+        }),
+        signer_seeds,
+        item
+    )
+}
+
+/// Arguments of a single NFT, passed as is to `mint_nft` and as a list to `batch_mint_nft`
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct MintNftItem {
+    pub name: String,
+    pub price: u64,
+    pub bid_step: u64,
+    pub reserve_price: Option<u64>,
+    pub buy_now_price: Option<u64>,
+    pub uri: String,
+    pub symbol: Option<String>,
+    pub creators: Option<Vec<NftCreator>>,
+}
+
+impl MintNftItem {
+    pub fn validate(&self) -> Result<()> {
+        if let Some(reserve_price) = self.reserve_price {
+            require!(reserve_price >= self.price, ErrorCode::InvalidReservePrice);
+        }
+
+        if let Some(buy_now_price) = self.buy_now_price {
+            require!(buy_now_price > self.price, ErrorCode::InvalidBuyNowPrice);
+            require!(
+                buy_now_price >= self.reserve_price.unwrap_or(0),
+                ErrorCode::InvalidBuyNowPrice
+            );
+        }
+
+        Ok(())
+    }
+
+    pub fn nft_info(
+        &self,
+        library_name: &str,
+        seller: Pubkey,
+        nft_mint: Pubkey,
+        payment_mint: Option<Pubkey>,
+        bump: u8
+    ) -> NftInfo {
+        NftInfo {
+            name: self.name.to_owned(),
+            current_bidder: seller,
+            bid_step: self.bid_step,
+            reserve_price: self.reserve_price,
+            buy_now_price: self.buy_now_price,
+            payment_mint,
+            current_price: self.price,
+            starting_price: self.price,
+            library_name: library_name.to_owned(),
+            bump,
+            nft_mint,
+            round: 0,
+        }
+    }
+}
+
+pub struct NftMintAccounts<'a, 'info> {
+    pub payer: &'a AccountInfo<'info>,
+    pub nft_mint: &'a AccountInfo<'info>,
+    pub associated_nft: &'a AccountInfo<'info>,
+    pub nft_metadata: &'a AccountInfo<'info>,
+    pub nft_master_edition: &'a AccountInfo<'info>,
+    pub library_account: &'a AccountInfo<'info>,
+    pub library_mint: &'a AccountInfo<'info>,
+    pub library_metadata: &'a AccountInfo<'info>,
+    pub library_master_edition: &'a AccountInfo<'info>,
+    pub seller_fee_basis_points: u16,
+    pub token_program: &'a AccountInfo<'info>,
+    pub metadata_program: &'a AccountInfo<'info>,
+    pub system_program: &'a AccountInfo<'info>,
+    pub rent: &'a AccountInfo<'info>,
+}
+
+/// Mints the token into the escrow and creates its metadata and master edition inside the library collection
+pub fn _mint_nft_item(
+    accounts: &NftMintAccounts,
+    signer_seeds: &[&[&[u8]]],
+    item: MintNftItem
+) -> Result<()> {
+    let symbol = _validate_metadata(&item.name, item.symbol, &item.uri)?;
+    // The minter is the default creator entitled to royalties, verified by signing below
+    let creators = _metadata_creators(item.creators, accounts.payer.key(), None)?;
+    let payer_is_creator = creators.iter().any(|creator| creator.address == accounts.payer.key());
 
     mint_to(
         CpiContext::new_with_signer(
-            ctx.accounts.token_program.to_account_info(),
+            accounts.token_program.to_account_info(),
             MintTo {
-                mint: ctx.accounts.nft_mint.to_account_info(),
-                to: ctx.accounts.associated_nft.to_account_info(),
-                authority: ctx.accounts.library_mint.to_account_info(),
+                mint: accounts.nft_mint.to_account_info(),
+                to: accounts.associated_nft.to_account_info(),
+                authority: accounts.library_mint.to_account_info(),
             },
             signer_seeds
         ),
@@ -168,24 +333,24 @@ pub fn _mint_nft(
 
     create_metadata_accounts_v3(
         CpiContext::new_with_signer(
-            ctx.accounts.metadata_program.to_account_info(),
+            accounts.metadata_program.to_account_info(),
             CreateMetadataAccountsV3 {
-                metadata: ctx.accounts.nft_metadata.to_account_info(),
-                mint: ctx.accounts.nft_mint.to_account_info(),
-                mint_authority: ctx.accounts.library_mint.to_account_info(),
-                payer: ctx.accounts.payer.to_account_info(),
-                update_authority: ctx.accounts.library_mint.to_account_info(),
-                system_program: ctx.accounts.system_program.to_account_info(),
-                rent: ctx.accounts.rent.to_account_info(),
+                metadata: accounts.nft_metadata.to_account_info(),
+                mint: accounts.nft_mint.to_account_info(),
+                mint_authority: accounts.library_mint.to_account_info(),
+                payer: accounts.payer.to_account_info(),
+                update_authority: accounts.library_mint.to_account_info(),
+                system_program: accounts.system_program.to_account_info(),
+                rent: accounts.rent.to_account_info(),
             },
             signer_seeds
         ),
         DataV2 {
-            name: name.to_owned(),
-            symbol: SYMBOL.to_string(),
-            uri: NFT_URL.to_string(),
-            seller_fee_basis_points: 0,
-            creators: None,
+            name: item.name.to_owned(),
+            symbol,
+            uri: item.uri,
+            seller_fee_basis_points: accounts.seller_fee_basis_points,
+            creators: Some(creators),
             collection: None,
             uses: None,
         },
@@ -196,49 +361,58 @@ pub fn _mint_nft(
 
     create_master_edition_v3(
         CpiContext::new_with_signer(
-            ctx.accounts.metadata_program.to_account_info(),
+            accounts.metadata_program.to_account_info(),
             CreateMasterEditionV3 {
-                payer: ctx.accounts.payer.to_account_info(),
-                mint: ctx.accounts.nft_mint.to_account_info(),
-                edition: ctx.accounts.nft_master_edition.to_account_info(),
-                mint_authority: ctx.accounts.library_mint.to_account_info(),
-                update_authority: ctx.accounts.library_mint.to_account_info(),
-                metadata: ctx.accounts.nft_metadata.to_account_info(),
-                token_program: ctx.accounts.token_program.to_account_info(),
-                system_program: ctx.accounts.system_program.to_account_info(),
-                rent: ctx.accounts.rent.to_account_info(),
+                payer: accounts.payer.to_account_info(),
+                mint: accounts.nft_mint.to_account_info(),
+                edition: accounts.nft_master_edition.to_account_info(),
+                mint_authority: accounts.library_mint.to_account_info(),
+                update_authority: accounts.library_mint.to_account_info(),
+                metadata: accounts.nft_metadata.to_account_info(),
+                token_program: accounts.token_program.to_account_info(),
+                system_program: accounts.system_program.to_account_info(),
+                rent: accounts.rent.to_account_info(),
             },
-            &signer_seeds
+            signer_seeds
         ),
         Some(0)
     )?;
 
     set_and_verify_sized_collection_item(
         CpiContext::new_with_signer(
-            ctx.accounts.metadata_program.to_account_info(),
+            accounts.metadata_program.to_account_info(),
             SetAndVerifySizedCollectionItem {
-                metadata: ctx.accounts.nft_metadata.to_account_info(),
-                collection_authority: ctx.accounts.library_mint.to_account_info(),
-                payer: ctx.accounts.payer.to_account_info(),
-                update_authority: ctx.accounts.library_mint.to_account_info(),
-                collection_mint: ctx.accounts.library_mint.to_account_info(),
-                collection_metadata: ctx.accounts.library_metadata.to_account_info(),
-                collection_master_edition: ctx.accounts.library_master_edition.to_account_info(),
+                metadata: accounts.nft_metadata.to_account_info(),
+                collection_authority: accounts.library_mint.to_account_info(),
+                payer: accounts.payer.to_account_info(),
+                update_authority: accounts.library_mint.to_account_info(),
+                collection_mint: accounts.library_mint.to_account_info(),
+                collection_metadata: accounts.library_metadata.to_account_info(),
+                collection_master_edition: accounts.library_master_edition.to_account_info(),
             },
             signer_seeds
         ),
         None
     )?;
 
+    if payer_is_creator {
+        sign_metadata(
+            CpiContext::new(accounts.metadata_program.to_account_info(), SignMetadata {
+                creator: accounts.payer.to_account_info(),
+                metadata: accounts.nft_metadata.to_account_info(),
+            })
+        )?;
+    }
+
     let clock = Clock::get()?;
 
     emit!(MintNftEvent {
-        name,
+        name: item.name,
         timestamp: clock.unix_timestamp,
-        library_address: ctx.accounts.library_account.key(),
-        nft_address: ctx.accounts.nft_mint.key(),
-        nft_price: price,
-        nft_bid_step: bid_step,
+        library_address: accounts.library_account.key(),
+        nft_address: accounts.nft_mint.key(),
+        nft_price: item.price,
+        nft_bid_step: item.bid_step,
     });
 
     Ok(())
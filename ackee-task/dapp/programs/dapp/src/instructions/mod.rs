@@ -4,11 +4,56 @@ pub use init_library::*;
 pub mod mint_nft;
 pub use mint_nft::*;
 
+pub mod batch_mint_nft;
+pub use batch_mint_nft::*;
+
 pub mod start_auction;
 pub use start_auction::*;
 
+pub mod reschedule_auction;
+pub use reschedule_auction::*;
+
 pub mod bid_nft;
 pub use bid_nft::*;
 
+pub mod commit_bid;
+pub use commit_bid::*;
+
+pub mod reveal_bid;
+pub use reveal_bid::*;
+
 pub mod transfer_nft;
 pub use transfer_nft::*;
+
+pub mod cancel_auction;
+pub use cancel_auction::*;
+
+pub mod settle_auction;
+pub use settle_auction::*;
+
+pub mod withdraw_refund;
+pub use withdraw_refund::*;
+
+pub mod relist;
+pub use relist::*;
+
+pub mod withdraw_unsold;
+pub use withdraw_unsold::*;
+
+pub mod buy_now;
+pub use buy_now::*;
+
+pub mod migrate_library;
+pub use migrate_library::*;
+
+pub mod migrate_nft;
+pub use migrate_nft::*;
+
+pub mod update_library;
+pub use update_library::*;
+
+pub mod rotate_library_authority;
+pub use rotate_library_authority::*;
+
+pub mod update_nft_metadata;
+pub use update_nft_metadata::*;
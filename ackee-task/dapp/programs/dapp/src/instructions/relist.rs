@@ -0,0 +1,89 @@
+use anchor_lang::prelude::*;
+
+use crate::enums::{ AuctionType, ErrorCode };
+use crate::state::{ AuctionState, NftInfo };
+use crate::constants::*;
+
+#[derive(Accounts)]
+#[instruction(name: String, library_name: String)]
+pub struct Relist<'info> {
+    #[account(mut)]
+    pub seller: Signer<'info>,
+
+    #[account(mut, seeds = [BUID_NFT_SEEDS, library_name.as_bytes(), name.as_bytes()], bump)]
+    pub nft_info: Account<'info, NftInfo>,
+
+    #[account(
+        mut,
+        seeds = [AUCTION_STATE_SEEDS, library_name.as_bytes(), name.as_bytes()],
+        bump,
+        constraint = auction_state.seller == seller.key() @ ErrorCode::UnauthorizedTransfer
+    )]
+    pub auction_state: Account<'info, AuctionState>,
+}
+
+pub fn _relist(
+    ctx: &mut Context<Relist>,
+    _name: String,
+    _library_name: String,
+    end_time: i64,
+    starting_price: Option<u64>
+) -> Result<()> {
+    let clock = Clock::get()?;
+    let nft_info = &mut ctx.accounts.nft_info;
+    let auction_state = &mut ctx.accounts.auction_state;
+
+    require!(clock.unix_timestamp > auction_state.ends_at(), ErrorCode::AuctionStillActive);
+    require!(nft_info.is_unsold(&auction_state.seller), ErrorCode::AuctionHasWinner);
+    require!(end_time > clock.unix_timestamp, ErrorCode::TimeSetIsNotValid);
+
+    if let Some(starting_price) = starting_price {
+        if let Some(reserve_price) = nft_info.reserve_price {
+            require!(reserve_price >= starting_price, ErrorCode::InvalidReservePrice);
+        }
+
+        if let Some(buy_now_price) = nft_info.buy_now_price {
+            require!(buy_now_price > starting_price, ErrorCode::InvalidBuyNowPrice);
+        }
+
+        // A Dutch auction descends from its own start price, not from the NFT starting price
+        if let AuctionType::Dutch { start_price, floor_price, .. } = &mut auction_state.auction_type {
+            require!(starting_price > *floor_price, ErrorCode::InvalidDutchAuction);
+
+            *start_price = starting_price;
+        }
+
+        nft_info.starting_price = starting_price;
+    }
+
+    // The NFT and the escrow stay where they are, only the schedule and the bids start over.
+    // The Dutch price drops again from the new start time and sealed commitments of the
+    // previous round go stale.
+    nft_info.reset_bids(auction_state.seller);
+
+    auction_state.auction_start_time = clock.unix_timestamp;
+    auction_state.auction_end_time = end_time;
+    auction_state.runner_up_price = 0;
+    nft_info.next_round()?;
+
+    emit!(AuctionRelisted {
+        nft_name: nft_info.name.clone(),
+        nft_address: nft_info.key(),
+        seller: auction_state.seller,
+        starting_price: nft_info.starting_price,
+        auction_end_time: end_time,
+        timestamp: clock.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+#[event]
+pub struct AuctionRelisted {
+    pub nft_name: String,
+    pub nft_address: Pubkey,
+    pub seller: Pubkey,
+    pub starting_price: u64,
+    pub auction_end_time: i64,
+    pub timestamp: i64,
+}
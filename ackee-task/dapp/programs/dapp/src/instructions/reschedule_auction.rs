@@ -0,0 +1,57 @@
+use anchor_lang::prelude::*;
+
+use crate::enums::ErrorCode;
+use crate::state::AuctionState;
+use crate::constants::*;
+
+#[derive(Accounts)]
+#[instruction(name: String, library_name: String)]
+pub struct RescheduleAuction<'info> {
+    pub seller: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [AUCTION_STATE_SEEDS, library_name.as_bytes(), name.as_bytes()],
+        bump,
+        constraint = auction_state.seller == seller.key() @ ErrorCode::UnauthorizedTransfer
+    )]
+    pub auction_state: Account<'info, AuctionState>,
+}
+
+pub fn _reschedule_auction(
+    ctx: &mut Context<RescheduleAuction>,
+    name: String,
+    _library_name: String,
+    start_time: Option<i64>,
+    end_time: i64
+) -> Result<()> {
+    let clock = Clock::get()?;
+    let auction_state = &mut ctx.accounts.auction_state;
+
+    // Once the auction is open bidders rely on its schedule
+    require!(!auction_state.has_started(clock.unix_timestamp), ErrorCode::AuctionAlreadyStarted);
+
+    let (start_time, end_time) = AuctionState::schedule(start_time, end_time, clock.unix_timestamp)?;
+
+    auction_state.auction_start_time = start_time;
+    auction_state.auction_end_time = end_time;
+
+    emit!(AuctionRescheduled {
+        nft_name: name,
+        seller: auction_state.seller,
+        auction_start_time: start_time,
+        auction_end_time: end_time,
+        timestamp: clock.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+#[event]
+pub struct AuctionRescheduled {
+    pub nft_name: String,
+    pub seller: Pubkey,
+    pub auction_start_time: i64,
+    pub auction_end_time: i64,
+    pub timestamp: i64,
+}
@@ -0,0 +1,102 @@
+use anchor_lang::prelude::*;
+
+use crate::enums::ErrorCode;
+use crate::state::{ AuctionState, NftInfo, PendingRefund, SealedBid };
+use crate::constants::*;
+
+#[derive(Accounts)]
+#[instruction(name: String, library_name: String)]
+pub struct RevealBid<'info> {
+    #[account(mut)]
+    pub bidder: Signer<'info>,
+
+    #[account(mut, seeds = [BUID_NFT_SEEDS, library_name.as_bytes(), name.as_bytes()], bump)]
+    pub nft_info: Account<'info, NftInfo>,
+
+    #[account(mut, seeds = [AUCTION_STATE_SEEDS, library_name.as_bytes(), name.as_bytes()], bump)]
+    pub auction_state: Account<'info, AuctionState>,
+
+    #[account(
+        seeds = [
+            PENDING_REFUND_SEEDS,
+            library_name.as_bytes(),
+            name.as_bytes(),
+            bidder.key().as_ref(),
+        ],
+        bump = pending_refund.bump,
+        has_one = bidder @ ErrorCode::InvalidPendingRefund
+    )]
+    pub pending_refund: Account<'info, PendingRefund>,
+
+    // A commitment opens once, closing it blocks a second reveal
+    #[account(
+        mut,
+        close = bidder,
+        seeds = [
+            SEALED_BID_SEEDS,
+            library_name.as_bytes(),
+            name.as_bytes(),
+            bidder.key().as_ref(),
+        ],
+        bump = sealed_bid.bump,
+        has_one = bidder @ ErrorCode::InvalidReveal
+    )]
+    pub sealed_bid: Account<'info, SealedBid>,
+}
+
+pub fn _reveal_bid(
+    ctx: &mut Context<RevealBid>,
+    _name: String,
+    _library_name: String,
+    amount: u64,
+    salt: [u8; 32]
+) -> Result<()> {
+    let clock = Clock::get()?;
+    let nft_info = &mut ctx.accounts.nft_info;
+    let auction_state = &mut ctx.accounts.auction_state;
+    let bidder = ctx.accounts.bidder.key();
+
+    require!(auction_state.is_revealing(clock.unix_timestamp), ErrorCode::RevealNotActive);
+    require!(ctx.accounts.sealed_bid.round == nft_info.round, ErrorCode::StaleSealedBid);
+    require!(
+        SealedBid::commitment(amount, &salt, &bidder) == ctx.accounts.sealed_bid.commitment,
+        ErrorCode::InvalidReveal
+    );
+    require!(amount <= ctx.accounts.pending_refund.amount, ErrorCode::InsufficientFunds);
+    require!(amount >= nft_info.starting_price, ErrorCode::BidBelowStartingPrice);
+
+    // The seller holds the current_bidder slot until the first reveal, ties go to the earlier reveal
+    let has_bids = nft_info.current_bidder != auction_state.seller;
+
+    if !has_bids || amount > nft_info.current_price {
+        if has_bids {
+            auction_state.runner_up_price = nft_info.current_price;
+        }
+
+        nft_info.current_bidder = bidder;
+        nft_info.current_price = amount;
+    } else if amount > auction_state.runner_up_price {
+        auction_state.runner_up_price = amount;
+    }
+
+    emit!(BidRevealed {
+        nft_name: nft_info.name.clone(),
+        nft_address: nft_info.key(),
+        bidder,
+        amount,
+        leading: nft_info.current_bidder == bidder,
+        timestamp: clock.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+#[event]
+pub struct BidRevealed {
+    pub nft_name: String,
+    pub nft_address: Pubkey,
+    pub bidder: Pubkey,
+    pub amount: u64,
+    pub leading: bool,
+    pub timestamp: i64,
+}
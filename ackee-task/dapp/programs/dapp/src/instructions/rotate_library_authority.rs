@@ -0,0 +1,54 @@
+use anchor_lang::prelude::*;
+
+use crate::enums::ErrorCode;
+use crate::{ constants::*, Library };
+
+#[derive(Accounts)]
+#[instruction(name: String)]
+pub struct RotateLibraryAuthority<'info> {
+    pub authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [LIBRARY_SEEDS, name.as_bytes()],
+        bump = library.bump,
+        has_one = authority @ ErrorCode::UnauthorizedLibraryAuthority
+    )]
+    pub library: Account<'info, Library>,
+}
+
+pub fn _rotate_library_authority(
+    ctx: &mut Context<RotateLibraryAuthority>,
+    _name: String,
+    new_authority: Pubkey
+) -> Result<()> {
+    require!(new_authority != Pubkey::default(), ErrorCode::UnauthorizedLibraryAuthority);
+
+    let library = &mut ctx.accounts.library;
+    let previous_authority = library.authority;
+
+    // Delegates were picked by the previous authority, the new one sets its own
+    library.authority = new_authority;
+    library.delegates.clear();
+
+    let clock = Clock::get()?;
+
+    emit!(LibraryAuthorityRotated {
+        name: library.name.clone(),
+        library_address: library.key(),
+        previous_authority,
+        new_authority,
+        timestamp: clock.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+#[event]
+pub struct LibraryAuthorityRotated {
+    pub name: String,
+    pub library_address: Pubkey,
+    pub previous_authority: Pubkey,
+    pub new_authority: Pubkey,
+    pub timestamp: i64,
+}
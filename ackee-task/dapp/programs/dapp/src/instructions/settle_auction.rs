@@ -0,0 +1,182 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    metadata::{ Metadata, MetadataAccount },
+    token_interface::{ Mint, TokenAccount, TokenInterface },
+};
+
+use crate::{
+    AUCTION_STATE_SEEDS,
+    BIDDER_ESCROW_SEEDS,
+    BUID_NFT_SEEDS,
+    LIBRARY_CONFIG_SEEDS,
+    LIBRARY_METADATA_SEEDS,
+    PENDING_REFUND_SEEDS,
+    AuctionState,
+    EscrowBidder,
+    LibraryConfig,
+    NftInfo,
+    PendingRefund,
+    SettlementAccounts,
+    _common_settlement_logic,
+    enums::ErrorCode,
+};
+use crate::utils::{ FeeAccounts, _token_payment };
+
+#[derive(Accounts)]
+#[instruction(name: String, library_name: String)]
+pub struct SettleAuction<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>, // Anyone can crank settlement once the auction is over
+
+    /// CHECK: Seller wallet, receives the winning bid
+    #[account(mut, address = auction_state.seller @ ErrorCode::UnauthorizedTransfer)]
+    pub seller: AccountInfo<'info>,
+
+    /// CHECK: Recipient wallet, must be the highest bidder
+    #[account(mut, address = nft_info.current_bidder @ ErrorCode::InvalidHighestBidder)]
+    pub recipient: AccountInfo<'info>,
+
+    /// CHECK: Wallet that paid the rent for the NFT accounts at mint time
+    #[account(mut, address = nft_bidder_escrow.payer @ ErrorCode::InvalidRentPayer)]
+    pub rent_payer: AccountInfo<'info>,
+
+    #[account(mut, address = nft_info.nft_mint)]
+    pub nft_mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        mut,
+        associated_token::mint = nft_mint,
+        associated_token::authority = nft_bidder_escrow,
+    )]
+    pub from_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        init_if_needed,
+        payer = payer,
+        associated_token::mint = nft_mint,
+        associated_token::authority = recipient,
+        associated_token::token_program = token_program
+    )]
+    pub to_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(mut, seeds = [BUID_NFT_SEEDS, library_name.as_bytes(), name.as_bytes()], bump)]
+    pub nft_info: Account<'info, NftInfo>,
+
+    #[account(
+        mut,
+        seeds = [
+            BIDDER_ESCROW_SEEDS,
+            library_name.as_bytes(),
+            name.as_bytes(),
+        ],
+        bump
+    )]
+    pub nft_bidder_escrow: Account<'info, EscrowBidder>,
+
+    // Closed on a sale, kept for `relist` or `withdraw_unsold` otherwise
+    #[account(
+        mut,
+        seeds = [AUCTION_STATE_SEEDS, library_name.as_bytes(), name.as_bytes()],
+        bump
+    )]
+    pub auction_state: Account<'info, AuctionState>,
+
+    #[account(
+        seeds = [LIBRARY_CONFIG_SEEDS, library_name.as_bytes()],
+        bump = library_config.bump
+    )]
+    pub library_config: Account<'info, LibraryConfig>,
+
+    #[account(
+        seeds = [
+            LIBRARY_METADATA_SEEDS,
+            metadata_program.key().as_ref(),
+            nft_mint.key().as_ref()
+        ],
+        bump,
+        seeds::program = metadata_program.key()
+    )]
+    pub nft_metadata: Box<Account<'info, MetadataAccount>>,
+
+    // Funds locked by the highest bidder, absent when nobody has bid
+    #[account(
+        mut,
+        seeds = [
+            PENDING_REFUND_SEEDS,
+            library_name.as_bytes(),
+            name.as_bytes(),
+            recipient.key().as_ref(),
+        ],
+        bump = winner_refund.bump
+    )]
+    pub winner_refund: Option<Account<'info, PendingRefund>>,
+
+    /// CHECK: Must match `LibraryConfig.protocol_fee_account`, only needed when a protocol fee is set
+    #[account(mut)]
+    pub protocol_fee_account: Option<UncheckedAccount<'info>>,
+
+    // Only for auctions priced in an SPL mint, see `NftInfo.payment_mint`
+    pub payment_mint: Option<InterfaceAccount<'info, Mint>>,
+
+    #[account(mut)]
+    pub escrow_payment_account: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(mut)]
+    pub seller_payment_account: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(mut)]
+    pub protocol_fee_payment_account: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+    pub metadata_program: Program<'info, Metadata>,
+    pub system_program: Program<'info, System>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+pub fn _settle_auction<'info>(
+    ctx: &mut Context<'_, '_, 'info, 'info, SettleAuction<'info>>,
+    _name: String,
+    _library_name: String
+) -> Result<()> {
+    let clock = Clock::get()?;
+
+    require!(
+        clock.unix_timestamp > ctx.accounts.auction_state.ends_at(),
+        ErrorCode::AuctionStillActive
+    );
+
+    let accounts = &mut ctx.accounts;
+
+    let token_payment = _token_payment(
+        accounts.nft_info.payment_mint,
+        &accounts.nft_bidder_escrow.key(),
+        &accounts.payment_mint,
+        &accounts.escrow_payment_account,
+        &accounts.token_program
+    )?;
+
+    _common_settlement_logic(SettlementAccounts {
+        nft_info: &mut accounts.nft_info,
+        nft_bidder_escrow: &mut accounts.nft_bidder_escrow,
+        auction_state: &mut accounts.auction_state,
+        nft_mint: &accounts.nft_mint,
+        from_token_account: &accounts.from_token_account,
+        to_token_account: &accounts.to_token_account.to_account_info(),
+        recipient: &accounts.recipient,
+        winner_refund: accounts.winner_refund.as_mut(),
+        seller: &accounts.seller,
+        seller_payment_account: accounts.seller_payment_account.as_ref(),
+        rent_payer: &accounts.rent_payer,
+        token_program: &accounts.token_program,
+        token_payment,
+        fee_accounts: FeeAccounts {
+            library_config: &accounts.library_config,
+            nft_metadata: &accounts.nft_metadata,
+            protocol_fee_account: accounts.protocol_fee_account.as_deref(),
+            protocol_fee_payment_account: accounts.protocol_fee_payment_account.as_ref(),
+            creator_accounts: ctx.remaining_accounts,
+        },
+    })
+}
@@ -1,27 +1,35 @@
 use anchor_lang::prelude::*;
 use anchor_spl::{ token_interface::{ TokenAccount } };
 
-use crate::{ AuctionState, constants::* };
+use crate::{ AuctionGate, AuctionState, AuctionType, NftInfo, constants::* };
 use crate::enums::ErrorCode;
 
 #[derive(Accounts)]
-#[instruction(name: String, end_time: i64)]
+#[instruction(name: String, library_name: String)]
 pub struct StartAuction<'info> {
     #[account(mut)]
     pub seller: Signer<'info>,
 
+    // The seller holds the current_bidder slot until the first bid
+    #[account(
+        seeds = [BUID_NFT_SEEDS, library_name.as_bytes(), name.as_bytes()],
+        bump,
+        constraint = nft_info.current_bidder == seller.key() @ ErrorCode::UnauthorizedTransfer
+    )]
+    pub nft_info: Account<'info, NftInfo>,
+
     #[account(
         init,
         payer = seller,
         space = 8 + AuctionState::INIT_SPACE,
-        seeds = [AUCTION_STATE_SEEDS, name.as_bytes()],
+        seeds = [AUCTION_STATE_SEEDS, library_name.as_bytes(), name.as_bytes()],
         bump
     )]
     pub auction_state: Account<'info, AuctionState>,
 
     #[account(
         mut,
-        seeds = [LIBRARY_ACCOUNT_SEEDS, name.as_bytes()],
+        seeds = [LIBRARY_ACCOUNT_SEEDS, library_name.as_bytes()],
         bump,
         constraint = library_account.mint != Pubkey::default() @ ErrorCode::LibraryAccountNotFound,
         constraint = library_account.amount > 0 @ ErrorCode::LibraryAccountNotFound
@@ -31,18 +39,47 @@ pub struct StartAuction<'info> {
     pub system_program: Program<'info, System>,
 }
 
-pub fn _start_auction(ctx: &mut Context<StartAuction>, _name: String, end_time: i64) -> Result<()> {
+#[allow(clippy::too_many_arguments)]
+pub fn _start_auction(
+    ctx: &mut Context<StartAuction>,
+    _name: String,
+    _library_name: String,
+    start_time: Option<i64>,
+    end_time: i64,
+    extension_window: i64,
+    auction_type: AuctionType,
+    gate: Option<AuctionGate>
+) -> Result<()> {
     let auction_state: &mut Account<'_, AuctionState> = &mut ctx.accounts.auction_state;
     let clock: Clock = Clock::get()?;
 
-    // Check if auction time is in the future
-    require!(end_time > clock.unix_timestamp, ErrorCode::TimeSetIsNotValid);
+    // Scheduled drops open at `start_time`, bids before it are rejected
+    let (start_time, end_time) = AuctionState::schedule(start_time, end_time, clock.unix_timestamp)?;
+    require!(extension_window >= 0, ErrorCode::InvalidExtensionWindow);
+
+    if let AuctionType::Dutch { start_price, floor_price, price_drop, drop_interval } = auction_type {
+        require!(start_price > floor_price, ErrorCode::InvalidDutchAuction);
+        require!(price_drop > 0 && drop_interval > 0, ErrorCode::InvalidDutchAuction);
+    }
+
+    if let AuctionType::Sealed { reveal_window, .. } = auction_type {
+        require!(reveal_window > 0, ErrorCode::TimeSetIsNotValid);
+    }
 
-    msg!("Auction end time: {} vs {} current time", end_time, clock.unix_timestamp);
+    msg!(
+        "Auction start time: {}, end time: {} vs {} current time",
+        start_time,
+        end_time,
+        clock.unix_timestamp
+    );
 
     // Set auction state
     auction_state.seller = ctx.accounts.seller.key();
+    auction_state.auction_start_time = start_time;
     auction_state.auction_end_time = end_time;
+    auction_state.extension_window = extension_window;
+    auction_state.auction_type = auction_type;
+    auction_state.gate = gate;
 
     Ok(())
 }
@@ -1,33 +1,57 @@
 use anchor_lang::prelude::*;
 use anchor_spl::{
     associated_token::AssociatedToken,
-    token_interface::{ Mint, TokenAccount, TokenInterface, transfer_checked, TransferChecked },
+    metadata::{ Metadata, MetadataAccount },
+    token_interface::{
+        Mint,
+        TokenAccount,
+        TokenInterface,
+        transfer_checked,
+        TransferChecked,
+        close_account,
+        CloseAccount,
+    },
 };
 
 use crate::{
     AUCTION_STATE_SEEDS,
     BIDDER_ESCROW_SEEDS,
     BUID_NFT_SEEDS,
+    LIBRARY_CONFIG_SEEDS,
+    LIBRARY_METADATA_SEEDS,
+    PENDING_REFUND_SEEDS,
     AuctionState,
     EscrowBidder,
+    LibraryConfig,
     NftInfo,
+    PendingRefund,
     enums::ErrorCode,
 };
+use crate::utils::{
+    FeeAccounts,
+    TokenPayment,
+    _close_escrow_payment_account,
+    _escrow_is_drained,
+    _pay_from_escrow,
+    _pay_settlement_fees,
+    _token_payment,
+};
 
 #[derive(Accounts)]
-#[instruction(name: String)]
+#[instruction(name: String, library_name: String)]
 pub struct TransferNft<'info> {
     #[account(mut)]
     pub owner: Signer<'info>, // Current owner of the NFT
 
-    /// CHECK: Recipient wallet
+    /// CHECK: Recipient wallet, must be the highest bidder
+    #[account(mut, address = nft_info.current_bidder @ ErrorCode::InvalidHighestBidder)]
     pub recipient: AccountInfo<'info>,
 
-    #[account(
-        mut,
-        seeds = [name.as_bytes()],
-        bump
-    )]
+    /// CHECK: Wallet that paid the rent for the NFT accounts at mint time
+    #[account(mut, address = nft_bidder_escrow.payer @ ErrorCode::InvalidRentPayer)]
+    pub rent_payer: AccountInfo<'info>,
+
+    #[account(mut, address = nft_info.nft_mint)]
     pub nft_mint: InterfaceAccount<'info, Mint>,
 
     #[account(
@@ -46,36 +70,86 @@ pub struct TransferNft<'info> {
     )]
     pub to_token_account: InterfaceAccount<'info, TokenAccount>,
 
-    #[account(mut, close = owner, seeds = [BUID_NFT_SEEDS, name.as_bytes()], bump)]
+    #[account(mut, seeds = [BUID_NFT_SEEDS, library_name.as_bytes(), name.as_bytes()], bump)]
     nft_info: Account<'info, NftInfo>,
 
     #[account(
         mut,
-        close = owner,
         seeds = [
             BIDDER_ESCROW_SEEDS,
-            nft_info.library_name.as_bytes(),
+            library_name.as_bytes(),
             name.as_bytes(),
         ],
         bump
     )]
     pub nft_bidder_escrow: Account<'info, EscrowBidder>,
 
+    // Closed on a sale, kept for `relist` or `withdraw_unsold` otherwise
     #[account(
         mut,
-        close = owner,
-        seeds = [AUCTION_STATE_SEEDS, name.as_bytes()],
+        seeds = [AUCTION_STATE_SEEDS, library_name.as_bytes(), name.as_bytes()],
         bump
     )]
     pub auction_state: Account<'info, AuctionState>,
 
+    #[account(
+        seeds = [LIBRARY_CONFIG_SEEDS, library_name.as_bytes()],
+        bump = library_config.bump
+    )]
+    pub library_config: Account<'info, LibraryConfig>,
+
+    #[account(
+        seeds = [
+            LIBRARY_METADATA_SEEDS,
+            metadata_program.key().as_ref(),
+            nft_mint.key().as_ref()
+        ],
+        bump,
+        seeds::program = metadata_program.key()
+    )]
+    pub nft_metadata: Box<Account<'info, MetadataAccount>>,
+
+    // Funds locked by the highest bidder, absent when nobody has bid
+    #[account(
+        mut,
+        seeds = [
+            PENDING_REFUND_SEEDS,
+            library_name.as_bytes(),
+            name.as_bytes(),
+            recipient.key().as_ref(),
+        ],
+        bump = winner_refund.bump
+    )]
+    pub winner_refund: Option<Account<'info, PendingRefund>>,
+
+    /// CHECK: Must match `LibraryConfig.protocol_fee_account`, only needed when a protocol fee is set
+    #[account(mut)]
+    pub protocol_fee_account: Option<UncheckedAccount<'info>>,
+
+    // Only for auctions priced in an SPL mint, see `NftInfo.payment_mint`
+    pub payment_mint: Option<InterfaceAccount<'info, Mint>>,
+
+    #[account(mut)]
+    pub escrow_payment_account: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(mut)]
+    pub owner_payment_account: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(mut)]
+    pub protocol_fee_payment_account: Option<InterfaceAccount<'info, TokenAccount>>,
+
     pub token_program: Interface<'info, TokenInterface>,
+    pub metadata_program: Program<'info, Metadata>,
     pub system_program: Program<'info, System>,
     pub associated_token_program: Program<'info, AssociatedToken>,
     pub rent: Sysvar<'info, Rent>,
 }
 
-pub fn _transfer_nft(ctx: &mut Context<TransferNft>, library_name: String) -> Result<()> {
+pub fn _transfer_nft<'info>(
+    ctx: &mut Context<'_, '_, 'info, 'info, TransferNft<'info>>,
+    _name: String,
+    _library_name: String
+) -> Result<()> {
     let clock = Clock::get()?;
 
     require!(
@@ -84,54 +158,183 @@ pub fn _transfer_nft(ctx: &mut Context<TransferNft>, library_name: String) -> Re
     );
 
     require!(
-        clock.unix_timestamp > ctx.accounts.auction_state.auction_end_time,
-        ErrorCode::AuctionTimeExpired
+        clock.unix_timestamp > ctx.accounts.auction_state.ends_at(),
+        ErrorCode::AuctionStillActive
     );
 
+    let accounts = &mut ctx.accounts;
+
+    let token_payment = _token_payment(
+        accounts.nft_info.payment_mint,
+        &accounts.nft_bidder_escrow.key(),
+        &accounts.payment_mint,
+        &accounts.escrow_payment_account,
+        &accounts.token_program
+    )?;
+
+    _common_settlement_logic(SettlementAccounts {
+        nft_info: &mut accounts.nft_info,
+        nft_bidder_escrow: &mut accounts.nft_bidder_escrow,
+        auction_state: &mut accounts.auction_state,
+        nft_mint: &accounts.nft_mint,
+        from_token_account: &accounts.from_token_account,
+        to_token_account: &accounts.to_token_account.to_account_info(),
+        recipient: &accounts.recipient,
+        winner_refund: accounts.winner_refund.as_mut(),
+        seller: &accounts.owner.to_account_info(),
+        seller_payment_account: accounts.owner_payment_account.as_ref(),
+        rent_payer: &accounts.rent_payer,
+        token_program: &accounts.token_program,
+        token_payment,
+        fee_accounts: FeeAccounts {
+            library_config: &accounts.library_config,
+            nft_metadata: &accounts.nft_metadata,
+            protocol_fee_account: accounts.protocol_fee_account.as_deref(),
+            protocol_fee_payment_account: accounts.protocol_fee_payment_account.as_ref(),
+            creator_accounts: ctx.remaining_accounts,
+        },
+    })
+}
+
+pub struct SettlementAccounts<'a, 'info> {
+    pub nft_info: &'a mut Account<'info, NftInfo>,
+    pub nft_bidder_escrow: &'a mut Account<'info, EscrowBidder>,
+    pub auction_state: &'a mut Account<'info, AuctionState>,
+    pub nft_mint: &'a InterfaceAccount<'info, Mint>,
+    pub from_token_account: &'a InterfaceAccount<'info, TokenAccount>,
+    pub to_token_account: &'a AccountInfo<'info>,
+    pub recipient: &'a AccountInfo<'info>,
+    pub winner_refund: Option<&'a mut Account<'info, PendingRefund>>,
+    pub seller: &'a AccountInfo<'info>,
+    pub seller_payment_account: Option<&'a InterfaceAccount<'info, TokenAccount>>,
+    pub rent_payer: &'a AccountInfo<'info>,
+    pub token_program: &'a Interface<'info, TokenInterface>,
+    pub token_payment: Option<TokenPayment<'a, 'info>>,
+    pub fee_accounts: FeeAccounts<'a, 'info>,
+}
+
+pub fn _common_settlement_logic(accounts: SettlementAccounts) -> Result<()> {
+    let clock = Clock::get()?;
+    let nft_info = accounts.nft_info;
+    let nft_bidder_escrow = accounts.nft_bidder_escrow;
+
+    // Unsold NFTs stay in escrow for `relist` or `withdraw_unsold`, a top bid below
+    // the reserve becomes withdrawable
+    if nft_info.is_unsold(&accounts.seller.key()) {
+        nft_info.reset_bids(accounts.seller.key());
+
+        msg!("No winning bid, NFT stays in escrow - {}", nft_info.name);
+
+        return Ok(());
+    }
+
+    let final_price = accounts.auction_state.clearing_price(nft_info);
+    let winner_refund = accounts.winner_refund.ok_or(ErrorCode::InvalidPendingRefund)?;
+
+    winner_refund.amount = winner_refund.amount
+        .checked_sub(final_price)
+        .ok_or(ErrorCode::InsufficientFunds)?;
+
     let signer_seeds: &[&[&[u8]]] = &[
         &[
             BIDDER_ESCROW_SEEDS,
-            library_name.as_bytes(),
-            ctx.accounts.nft_info.name.as_bytes(),
-            &[ctx.bumps.nft_bidder_escrow],
+            nft_info.library_name.as_bytes(),
+            nft_info.name.as_bytes(),
+            &[nft_bidder_escrow.bump],
         ],
     ];
 
-    if ctx.accounts.nft_info.current_bidder != ctx.accounts.owner.key() {
-        let transfer_account_options = TransferChecked {
-            from: ctx.accounts.from_token_account.to_account_info(),
-            to: ctx.accounts.to_token_account.to_account_info(),
-            authority: ctx.accounts.nft_bidder_escrow.to_account_info(),
-            mint: ctx.accounts.nft_mint.to_account_info(),
-        };
-
-        let cpi_context: CpiContext<
-            '_,
-            '_,
-            '_,
-            '_,
-            TransferChecked<'_>
-        > = CpiContext::new_with_signer(
-            ctx.accounts.token_program.to_account_info(),
-            transfer_account_options,
+    transfer_checked(
+        CpiContext::new_with_signer(
+            accounts.token_program.to_account_info(),
+            TransferChecked {
+                from: accounts.from_token_account.to_account_info(),
+                to: accounts.to_token_account.to_account_info(),
+                authority: nft_bidder_escrow.to_account_info(),
+                mint: accounts.nft_mint.to_account_info(),
+            },
+            signer_seeds
+        ),
+        1,
+        0
+    )?;
+
+    // The emptied escrow token account goes back to whoever funded it
+    close_account(
+        CpiContext::new_with_signer(
+            accounts.token_program.to_account_info(),
+            CloseAccount {
+                account: accounts.from_token_account.to_account_info(),
+                destination: accounts.rent_payer.to_account_info(),
+                authority: nft_bidder_escrow.to_account_info(),
+            },
             signer_seeds
-        );
-
-        transfer_checked(cpi_context, 1, 0)?;
-
-        emit!(TransferNftEvent {
-            nft_name: ctx.accounts.nft_info.name.clone(),
-            recipient: ctx.accounts.recipient.key(),
-            timestamp: Clock::get()?.unix_timestamp,
-            owner: ctx.accounts.owner.key(),
-        });
-    } else {
-        msg!("Current bidder is the owner, no transfer needed - {}", ctx.accounts.owner.key());
+        )
+    )?;
+
+    let fees = _pay_settlement_fees(
+        nft_bidder_escrow,
+        nft_info,
+        &accounts.token_payment,
+        &accounts.fee_accounts,
+        final_price
+    )?;
+    let seller_amount = final_price
+        .checked_sub(fees.royalty_amount)
+        .and_then(|amount| amount.checked_sub(fees.protocol_fee))
+        .ok_or(ErrorCode::FeesExceedFinalPrice)?;
+
+    // The rest of the winning bid goes to the seller
+    _pay_from_escrow(
+        nft_bidder_escrow,
+        &nft_info.library_name,
+        &nft_info.name,
+        accounts.seller,
+        accounts.seller_payment_account,
+        &accounts.token_payment,
+        seller_amount
+    )?;
+
+    // Anything the winner locked beyond the final price stays withdrawable
+    if winner_refund.amount == 0 {
+        winner_refund.close(accounts.recipient.to_account_info())?;
     }
 
-    msg!("Transferred NFT to: {:?}", ctx.accounts.recipient.key());
+    emit!(TransferNftEvent {
+        nft_name: nft_info.name.clone(),
+        recipient: accounts.recipient.key(),
+        timestamp: clock.unix_timestamp,
+        owner: accounts.seller.key(),
+    });
+
+    emit!(AuctionSettled {
+        nft_name: nft_info.name.clone(),
+        nft_address: nft_info.key(),
+        seller: accounts.seller.key(),
+        winner: accounts.recipient.key(),
+        final_price,
+        royalty_amount: fees.royalty_amount,
+        protocol_fee: fees.protocol_fee,
+        timestamp: clock.unix_timestamp,
+    });
+
+    msg!("Transferred NFT to: {:?}", accounts.recipient.key());
 
-    Ok(())
+    // Outbid funds keep the escrow open until the last `withdraw_refund` closes it
+    if _escrow_is_drained(nft_bidder_escrow, &accounts.token_payment)? {
+        _close_escrow_payment_account(
+            nft_bidder_escrow,
+            &nft_info.library_name,
+            &nft_info.name,
+            &accounts.token_payment,
+            accounts.rent_payer
+        )?;
+
+        nft_bidder_escrow.close(accounts.rent_payer.to_account_info())?;
+    }
+
+    nft_info.close(accounts.rent_payer.to_account_info())?;
+    accounts.auction_state.close(accounts.seller.to_account_info())
 }
 
 #[event]
@@ -141,3 +344,15 @@ pub struct TransferNftEvent {
     pub owner: Pubkey,
     pub timestamp: i64,
 }
+
+#[event]
+pub struct AuctionSettled {
+    pub nft_name: String,
+    pub nft_address: Pubkey,
+    pub seller: Pubkey,
+    pub winner: Pubkey,
+    pub final_price: u64,
+    pub royalty_amount: u64,
+    pub protocol_fee: u64,
+    pub timestamp: i64,
+}
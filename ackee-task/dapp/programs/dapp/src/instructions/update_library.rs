@@ -0,0 +1,69 @@
+use anchor_lang::prelude::*;
+
+use crate::enums::ErrorCode;
+use crate::{ constants::*, Library };
+
+/// Edits the `Library` registry account only. Description, url, icon and delegates have no
+/// counterpart in Metaplex, the collection metadata written by `init_library` is left as is.
+#[derive(Accounts)]
+#[instruction(name: String)]
+pub struct UpdateLibrary<'info> {
+    pub authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [LIBRARY_SEEDS, name.as_bytes()],
+        bump = library.bump,
+        has_one = authority @ ErrorCode::UnauthorizedLibraryAuthority
+    )]
+    pub library: Account<'info, Library>,
+}
+
+pub fn _update_library(
+    ctx: &mut Context<UpdateLibrary>,
+    _name: String,
+    description: Option<String>,
+    url: Option<String>,
+    icon: Option<String>,
+    delegates: Option<Vec<Pubkey>>
+) -> Result<()> {
+    let library = &mut ctx.accounts.library;
+
+    if let Some(description) = description {
+        Library::validate_field(&description)?;
+        library.description = description;
+    }
+
+    if let Some(url) = url {
+        Library::validate_field(&url)?;
+        library.url = url;
+    }
+
+    if let Some(icon) = icon {
+        Library::validate_field(&icon)?;
+        library.icon = icon;
+    }
+
+    if let Some(delegates) = delegates {
+        library.set_delegates(delegates)?;
+    }
+
+    let clock = Clock::get()?;
+
+    emit!(LibraryUpdated {
+        name: library.name.clone(),
+        library_address: library.key(),
+        authority: library.authority,
+        timestamp: clock.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+#[event]
+pub struct LibraryUpdated {
+    pub name: String,
+    pub library_address: Pubkey,
+    pub authority: Pubkey,
+    pub timestamp: i64,
+}
@@ -0,0 +1,120 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    metadata::{
+        Metadata,
+        MetadataAccount,
+        UpdateMetadataAccountsV2,
+        update_metadata_accounts_v2,
+        mpl_token_metadata::types::DataV2,
+    },
+    token_interface::Mint,
+};
+
+use crate::enums::ErrorCode;
+use crate::state::{ Library, NftInfo };
+use crate::constants::*;
+use crate::utils::_validate_metadata;
+
+#[derive(Accounts)]
+#[instruction(name: String, library_name: String)]
+pub struct UpdateNftMetadata<'info> {
+    pub authority: Signer<'info>,
+
+    #[account(
+        seeds = [LIBRARY_SEEDS, library_name.as_bytes()],
+        bump = library.bump,
+        has_one = authority @ ErrorCode::UnauthorizedLibraryAuthority
+    )]
+    pub library: Account<'info, Library>,
+
+    #[account(seeds = [BUID_NFT_SEEDS, library_name.as_bytes(), name.as_bytes()], bump)]
+    pub nft_info: Account<'info, NftInfo>,
+
+    #[account(address = nft_info.nft_mint)]
+    pub nft_mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        mut,
+        seeds = [
+            LIBRARY_METADATA_SEEDS,
+            metadata_program.key().as_ref(),
+            nft_mint.key().as_ref()
+        ],
+        bump,
+        seeds::program = metadata_program.key()
+    )]
+    pub nft_metadata: Box<Account<'info, MetadataAccount>>,
+
+    // Update authority of every NFT metadata in the library
+    #[account(seeds = [LIBRARY_MINT_SEEDS, library_name.as_bytes()], bump)]
+    pub library_mint: InterfaceAccount<'info, Mint>,
+
+    pub metadata_program: Program<'info, Metadata>,
+}
+
+pub fn _update_nft_metadata(
+    ctx: &mut Context<UpdateNftMetadata>,
+    name: String,
+    library_name: String,
+    uri: Option<String>,
+    symbol: Option<String>
+) -> Result<()> {
+    let nft_metadata = &ctx.accounts.nft_metadata;
+
+    // Metaplex pads the stored strings with null bytes
+    let current_symbol = nft_metadata.symbol.trim_end_matches('\0').to_string();
+    let current_uri = nft_metadata.uri.trim_end_matches('\0').to_string();
+
+    let uri = uri.unwrap_or(current_uri);
+    let symbol = _validate_metadata(&name, Some(symbol.unwrap_or(current_symbol)), &uri)?;
+
+    let signer_seeds: &[&[&[u8]]] = &[
+        &[LIBRARY_MINT_SEEDS, library_name.as_bytes(), &[ctx.bumps.library_mint]],
+    ];
+
+    update_metadata_accounts_v2(
+        CpiContext::new_with_signer(
+            ctx.accounts.metadata_program.to_account_info(),
+            UpdateMetadataAccountsV2 {
+                metadata: nft_metadata.to_account_info(),
+                update_authority: ctx.accounts.library_mint.to_account_info(),
+            },
+            signer_seeds
+        ),
+        None,
+        Some(DataV2 {
+            name: nft_metadata.name.trim_end_matches('\0').to_string(),
+            symbol: symbol.to_owned(),
+            uri: uri.to_owned(),
+            seller_fee_basis_points: nft_metadata.seller_fee_basis_points,
+            creators: nft_metadata.creators.clone(),
+            collection: nft_metadata.collection.clone(),
+            uses: nft_metadata.uses.clone(),
+        }),
+        None,
+        None
+    )?;
+
+    let clock = Clock::get()?;
+
+    emit!(NftMetadataUpdated {
+        name,
+        library_name,
+        nft_address: ctx.accounts.nft_mint.key(),
+        symbol,
+        uri,
+        timestamp: clock.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+#[event]
+pub struct NftMetadataUpdated {
+    pub name: String,
+    pub library_name: String,
+    pub nft_address: Pubkey,
+    pub symbol: String,
+    pub uri: String,
+    pub timestamp: i64,
+}
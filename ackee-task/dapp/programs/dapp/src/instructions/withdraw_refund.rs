@@ -0,0 +1,147 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{ Mint, TokenAccount, TokenInterface };
+
+use crate::enums::ErrorCode;
+use crate::state::{ EscrowBidder, NftInfo, PendingRefund, SealedBid };
+use crate::constants::*;
+use crate::utils::{
+    _close_escrow_payment_account,
+    _escrow_is_drained,
+    _pay_from_escrow,
+    _token_payment,
+};
+
+#[derive(Accounts)]
+#[instruction(name: String, library_name: String)]
+pub struct WithdrawRefund<'info> {
+    #[account(mut)]
+    pub bidder: Signer<'info>,
+
+    #[account(
+        mut,
+        close = bidder,
+        seeds = [
+            PENDING_REFUND_SEEDS,
+            library_name.as_bytes(),
+            name.as_bytes(),
+            bidder.key().as_ref(),
+        ],
+        bump = pending_refund.bump,
+        has_one = bidder @ ErrorCode::InvalidPendingRefund
+    )]
+    pub pending_refund: Account<'info, PendingRefund>,
+
+    // An unrevealed sealed bid commitment, closed along with the deposit
+    #[account(
+        mut,
+        close = bidder,
+        seeds = [
+            SEALED_BID_SEEDS,
+            library_name.as_bytes(),
+            name.as_bytes(),
+            bidder.key().as_ref(),
+        ],
+        bump = sealed_bid.bump
+    )]
+    pub sealed_bid: Option<Account<'info, SealedBid>>,
+
+    #[account(
+        mut,
+        seeds = [
+            BIDDER_ESCROW_SEEDS,
+            library_name.as_bytes(),
+            name.as_bytes(),
+        ],
+        bump = nft_bidder_escrow.bump
+    )]
+    pub nft_bidder_escrow: Account<'info, EscrowBidder>,
+
+    /// CHECK: Closed once the auction has settled, otherwise read to keep the leading bid locked
+    #[account(seeds = [BUID_NFT_SEEDS, library_name.as_bytes(), name.as_bytes()], bump)]
+    pub nft_info: UncheckedAccount<'info>,
+
+    /// CHECK: Wallet that paid the rent for the NFT accounts at mint time
+    #[account(mut, address = nft_bidder_escrow.payer @ ErrorCode::InvalidRentPayer)]
+    pub rent_payer: AccountInfo<'info>,
+
+    // Only for auctions priced in an SPL mint, see `PendingRefund.payment_mint`
+    pub payment_mint: Option<InterfaceAccount<'info, Mint>>,
+
+    #[account(mut)]
+    pub escrow_payment_account: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(mut)]
+    pub bidder_payment_account: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+}
+
+pub fn _withdraw_refund(
+    ctx: &mut Context<WithdrawRefund>,
+    name: String,
+    library_name: String
+) -> Result<()> {
+    let clock = Clock::get()?;
+    let accounts = &mut ctx.accounts;
+
+    let nft_info = accounts.nft_info.to_account_info();
+    let auction_open = nft_info.owner == &crate::ID && !nft_info.data_is_empty();
+
+    // The leading bid stays locked until it is outbid, settled or the auction is cancelled
+    if auction_open {
+        let nft_info = NftInfo::try_deserialize(&mut &nft_info.try_borrow_data()?[..])?;
+
+        require_keys_neq!(nft_info.current_bidder, accounts.bidder.key(), ErrorCode::RefundLocked);
+    }
+
+    let amount = accounts.pending_refund.amount;
+
+    let token_payment = _token_payment(
+        accounts.pending_refund.payment_mint,
+        &accounts.nft_bidder_escrow.key(),
+        &accounts.payment_mint,
+        &accounts.escrow_payment_account,
+        &accounts.token_program
+    )?;
+
+    _pay_from_escrow(
+        &accounts.nft_bidder_escrow,
+        &library_name,
+        &name,
+        &accounts.bidder.to_account_info(),
+        accounts.bidder_payment_account.as_ref(),
+        &token_payment,
+        amount
+    )?;
+
+    // The last withdrawal after settlement hands the escrow rent back
+    if !auction_open && _escrow_is_drained(&accounts.nft_bidder_escrow, &token_payment)? {
+        _close_escrow_payment_account(
+            &accounts.nft_bidder_escrow,
+            &library_name,
+            &name,
+            &token_payment,
+            &accounts.rent_payer
+        )?;
+
+        accounts.nft_bidder_escrow.close(accounts.rent_payer.to_account_info())?;
+    }
+
+    emit!(RefundWithdrawn {
+        nft_name: name,
+        bidder: accounts.bidder.key(),
+        amount,
+        timestamp: clock.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+#[event]
+pub struct RefundWithdrawn {
+    pub nft_name: String,
+    pub bidder: Pubkey,
+    pub amount: u64,
+    pub timestamp: i64,
+}
@@ -0,0 +1,135 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token_interface::{ Mint, TokenAccount, TokenInterface },
+};
+
+use crate::enums::ErrorCode;
+use crate::state::{ AuctionState, EscrowBidder, NftInfo };
+use crate::constants::*;
+use crate::utils::{ UnsoldNftAccounts, _return_unsold_nft, _token_payment };
+
+#[derive(Accounts)]
+#[instruction(name: String, library_name: String)]
+pub struct WithdrawUnsold<'info> {
+    #[account(mut)]
+    pub seller: Signer<'info>,
+
+    #[account(
+        mut,
+        close = rent_payer,
+        seeds = [BUID_NFT_SEEDS, library_name.as_bytes(), name.as_bytes()],
+        bump
+    )]
+    pub nft_info: Account<'info, NftInfo>,
+
+    #[account(
+        mut,
+        close = seller,
+        seeds = [AUCTION_STATE_SEEDS, library_name.as_bytes(), name.as_bytes()],
+        bump,
+        constraint = auction_state.seller == seller.key() @ ErrorCode::UnauthorizedTransfer
+    )]
+    pub auction_state: Account<'info, AuctionState>,
+
+    #[account(
+        mut,
+        seeds = [
+            BIDDER_ESCROW_SEEDS,
+            library_name.as_bytes(),
+            name.as_bytes(),
+        ],
+        bump = nft_bidder_escrow.bump
+    )]
+    pub nft_bidder_escrow: Account<'info, EscrowBidder>,
+
+    /// CHECK: Wallet that paid the rent for the NFT accounts at mint time
+    #[account(mut, address = nft_bidder_escrow.payer @ ErrorCode::InvalidRentPayer)]
+    pub rent_payer: AccountInfo<'info>,
+
+    #[account(address = nft_info.nft_mint)]
+    pub nft_mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        mut,
+        associated_token::mint = nft_mint,
+        associated_token::authority = nft_bidder_escrow,
+    )]
+    pub from_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        init_if_needed,
+        payer = seller,
+        associated_token::mint = nft_mint,
+        associated_token::authority = seller,
+        associated_token::token_program = token_program
+    )]
+    pub to_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    // Only for auctions priced in an SPL mint, see `NftInfo.payment_mint`
+    pub payment_mint: Option<InterfaceAccount<'info, Mint>>,
+
+    #[account(mut)]
+    pub escrow_payment_account: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+pub fn _withdraw_unsold(
+    ctx: &mut Context<WithdrawUnsold>,
+    name: String,
+    library_name: String
+) -> Result<()> {
+    let clock = Clock::get()?;
+    let accounts = &mut ctx.accounts;
+
+    require!(
+        clock.unix_timestamp > accounts.auction_state.ends_at(),
+        ErrorCode::AuctionStillActive
+    );
+    require!(
+        accounts.nft_info.is_unsold(&accounts.seller.key()),
+        ErrorCode::AuctionHasWinner
+    );
+
+    let token_payment = _token_payment(
+        accounts.nft_info.payment_mint,
+        &accounts.nft_bidder_escrow.key(),
+        &accounts.payment_mint,
+        &accounts.escrow_payment_account,
+        &accounts.token_program
+    )?;
+
+    _return_unsold_nft(
+        UnsoldNftAccounts {
+            nft_bidder_escrow: &accounts.nft_bidder_escrow,
+            rent_payer: &accounts.rent_payer,
+            nft_mint: &accounts.nft_mint,
+            from_token_account: &accounts.from_token_account,
+            to_token_account: &accounts.to_token_account,
+            token_program: &accounts.token_program,
+            token_payment,
+        },
+        &library_name,
+        &name
+    )?;
+
+    emit!(UnsoldWithdrawn {
+        nft_name: name,
+        nft_address: accounts.nft_info.key(),
+        seller: accounts.seller.key(),
+        timestamp: clock.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+#[event]
+pub struct UnsoldWithdrawn {
+    pub nft_name: String,
+    pub nft_address: Pubkey,
+    pub seller: Pubkey,
+    pub timestamp: i64,
+}
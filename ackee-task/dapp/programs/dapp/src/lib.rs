@@ -12,43 +12,258 @@ pub use state::*;
 pub mod enums;
 pub use enums::*;
 
+pub mod utils;
+pub use utils::*;
+
 declare_id!("EDFwnAysttkv5TW7davfHDuFctxnZxNRb8WCU2AVf7um");
 
 #[program]
 pub mod dapp {
     use super::*;
 
-    pub fn init_library(ctx: Context<InitLibrary>, name: String) -> Result<()> {
-        _init_library(&ctx, name)
+    #[allow(clippy::too_many_arguments)]
+    pub fn init_library(
+        mut ctx: Context<InitLibrary>,
+        name: String,
+        description: String,
+        url: String,
+        icon: String,
+        uri: String,
+        symbol: Option<String>,
+        creators: Option<Vec<NftCreator>>,
+        seller_fee_basis_points: u16,
+        protocol_fee_basis_points: u16,
+        protocol_fee_account: Option<Pubkey>
+    ) -> Result<()> {
+        _init_library(
+            &mut ctx,
+            name,
+            description,
+            url,
+            icon,
+            uri,
+            symbol,
+            creators,
+            seller_fee_basis_points,
+            protocol_fee_basis_points,
+            protocol_fee_account
+        )
     }
 
+    #[allow(clippy::too_many_arguments)]
     pub fn mint_nft(
         mut ctx: Context<MintNft>,
         name: String,
         price: u64,
         library_name: String,
-        bid_step: u64
+        bid_step: u64,
+        reserve_price: Option<u64>,
+        buy_now_price: Option<u64>,
+        uri: String,
+        symbol: Option<String>,
+        creators: Option<Vec<NftCreator>>
+    ) -> Result<()> {
+        _mint_nft(
+            &mut ctx,
+            name,
+            price,
+            library_name,
+            bid_step,
+            reserve_price,
+            buy_now_price,
+            uri,
+            symbol,
+            creators
+        )
+    }
+
+    pub fn batch_mint_nft<'info>(
+        mut ctx: Context<'_, '_, '_, 'info, BatchMintNft<'info>>,
+        library_name: String,
+        items: Vec<MintNftItem>
     ) -> Result<()> {
-        _mint_nft(&mut ctx, name, price, library_name, bid_step)
+        _batch_mint_nft(&mut ctx, library_name, items)
     }
 
-    pub fn bid_nft(mut ctx: Context<BidNft>, name: String) -> Result<()> {
-        _bid_nft(&mut ctx, name)
+    pub fn bid_nft<'info>(
+        mut ctx: Context<'_, '_, 'info, 'info, BidNft<'info>>,
+        name: String,
+        library_name: String,
+        bid_amount: u64,
+        merkle_proof: Option<Vec<[u8; 32]>>
+    ) -> Result<()> {
+        _bid_nft(&mut ctx, name, library_name, bid_amount, merkle_proof)
+    }
+
+    pub fn commit_bid(
+        mut ctx: Context<CommitBid>,
+        name: String,
+        library_name: String,
+        commitment: [u8; 32],
+        deposit: u64,
+        merkle_proof: Option<Vec<[u8; 32]>>
+    ) -> Result<()> {
+        _commit_bid(&mut ctx, name, library_name, commitment, deposit, merkle_proof)
+    }
+
+    pub fn reveal_bid(
+        mut ctx: Context<RevealBid>,
+        name: String,
+        library_name: String,
+        amount: u64,
+        salt: [u8; 32]
+    ) -> Result<()> {
+        _reveal_bid(&mut ctx, name, library_name, amount, salt)
     }
 
     // pub fn create_escrow_and_bid(mut ctx: Context<CreateEscrowAndBid>, name: String) -> Result<()> {
     //     _create_escrow_and_bid(&mut ctx, name)
     // }
 
+    #[allow(clippy::too_many_arguments)]
     pub fn start_auction(
         mut ctx: Context<StartAuction>,
         name: String,
+        library_name: String,
+        start_time: Option<i64>,
+        end_time: i64,
+        extension_window: i64,
+        auction_type: AuctionType,
+        gate: Option<AuctionGate>
+    ) -> Result<()> {
+        _start_auction(
+            &mut ctx,
+            name,
+            library_name,
+            start_time,
+            end_time,
+            extension_window,
+            auction_type,
+            gate
+        )
+    }
+
+    pub fn reschedule_auction(
+        mut ctx: Context<RescheduleAuction>,
+        name: String,
+        library_name: String,
+        start_time: Option<i64>,
         end_time: i64
     ) -> Result<()> {
-        _start_auction(&mut ctx, name, end_time)
+        _reschedule_auction(&mut ctx, name, library_name, start_time, end_time)
     }
 
-    pub fn transfer_nft(mut ctx: Context<TransferNft>, name: String) -> Result<()> {
-        _transfer_nft(&mut ctx, name)
+    pub fn transfer_nft<'info>(
+        mut ctx: Context<'_, '_, 'info, 'info, TransferNft<'info>>,
+        name: String,
+        library_name: String
+    ) -> Result<()> {
+        _transfer_nft(&mut ctx, name, library_name)
+    }
+
+    pub fn buy_now<'info>(
+        mut ctx: Context<'_, '_, 'info, 'info, BuyNow<'info>>,
+        name: String,
+        library_name: String,
+        merkle_proof: Option<Vec<[u8; 32]>>
+    ) -> Result<()> {
+        _buy_now(&mut ctx, name, library_name, merkle_proof)
+    }
+
+    pub fn settle_auction<'info>(
+        mut ctx: Context<'_, '_, 'info, 'info, SettleAuction<'info>>,
+        name: String,
+        library_name: String
+    ) -> Result<()> {
+        _settle_auction(&mut ctx, name, library_name)
+    }
+
+    pub fn cancel_auction(
+        mut ctx: Context<CancelAuction>,
+        name: String,
+        library_name: String
+    ) -> Result<()> {
+        _cancel_auction(&mut ctx, name, library_name)
+    }
+
+    pub fn withdraw_refund(
+        mut ctx: Context<WithdrawRefund>,
+        name: String,
+        library_name: String
+    ) -> Result<()> {
+        _withdraw_refund(&mut ctx, name, library_name)
+    }
+
+    pub fn relist(
+        mut ctx: Context<Relist>,
+        name: String,
+        library_name: String,
+        end_time: i64,
+        starting_price: Option<u64>
+    ) -> Result<()> {
+        _relist(&mut ctx, name, library_name, end_time, starting_price)
+    }
+
+    pub fn withdraw_unsold(
+        mut ctx: Context<WithdrawUnsold>,
+        name: String,
+        library_name: String
+    ) -> Result<()> {
+        _withdraw_unsold(&mut ctx, name, library_name)
+    }
+
+    pub fn migrate_library(
+        mut ctx: Context<MigrateLibrary>,
+        name: String,
+        authority: Pubkey,
+        seller_fee_basis_points: u16,
+        protocol_fee_basis_points: u16,
+        protocol_fee_account: Option<Pubkey>
+    ) -> Result<()> {
+        _migrate_library(
+            &mut ctx,
+            name,
+            authority,
+            seller_fee_basis_points,
+            protocol_fee_basis_points,
+            protocol_fee_account
+        )
+    }
+
+    pub fn migrate_nft(
+        mut ctx: Context<MigrateNft>,
+        name: String,
+        library_name: String
+    ) -> Result<()> {
+        _migrate_nft(&mut ctx, name, library_name)
+    }
+
+    pub fn update_library(
+        mut ctx: Context<UpdateLibrary>,
+        name: String,
+        description: Option<String>,
+        url: Option<String>,
+        icon: Option<String>,
+        delegates: Option<Vec<Pubkey>>
+    ) -> Result<()> {
+        _update_library(&mut ctx, name, description, url, icon, delegates)
+    }
+
+    pub fn rotate_library_authority(
+        mut ctx: Context<RotateLibraryAuthority>,
+        name: String,
+        new_authority: Pubkey
+    ) -> Result<()> {
+        _rotate_library_authority(&mut ctx, name, new_authority)
+    }
+
+    pub fn update_nft_metadata(
+        mut ctx: Context<UpdateNftMetadata>,
+        name: String,
+        library_name: String,
+        uri: Option<String>,
+        symbol: Option<String>
+    ) -> Result<()> {
+        _update_nft_metadata(&mut ctx, name, library_name, uri, symbol)
     }
 }
@@ -1,8 +1,85 @@
 use anchor_lang::prelude::*;
 
+use crate::enums::{ AuctionGate, AuctionType, ErrorCode };
+use crate::state::NftInfo;
+
 #[derive(InitSpace)]
 #[account]
 pub struct AuctionState {
+    pub nft_mint: Pubkey,
+    pub seller: Pubkey,
+    pub auction_start_time: i64,
+    pub auction_end_time: i64,
+    pub extension_window: i64,
+    pub auction_type: AuctionType,
+    pub bump: u8,
+    pub gate: Option<AuctionGate>,
+    // Second highest revealed bid of a sealed auction
+    pub runner_up_price: u64,
+}
+
+impl AuctionState {
+    /// Checks a start and end time pair, `None` opens the auction right away
+    pub fn schedule(start_time: Option<i64>, end_time: i64, now: i64) -> Result<(i64, i64)> {
+        let start_time = start_time.unwrap_or(now);
+
+        require!(start_time >= now, ErrorCode::TimeSetIsNotValid);
+        require!(end_time > start_time, ErrorCode::TimeSetIsNotValid);
+
+        Ok((start_time, end_time))
+    }
+
+    pub fn has_started(&self, now: i64) -> bool {
+        now >= self.auction_start_time
+    }
+
+    /// Settlement time, sealed auctions close once the reveal window is over
+    pub fn ends_at(&self) -> i64 {
+        match self.auction_type {
+            AuctionType::Sealed { reveal_window, .. } =>
+                self.auction_end_time.saturating_add(reveal_window),
+            _ => self.auction_end_time,
+        }
+    }
+
+    /// Reveals run between the end of the commit phase and `ends_at`
+    pub fn is_revealing(&self, now: i64) -> bool {
+        matches!(self.auction_type, AuctionType::Sealed { .. }) &&
+            now >= self.auction_end_time &&
+            now < self.ends_at()
+    }
+
+    /// What the winner pays, the runner-up bid in a second-price sealed auction
+    /// but never less than the reserve or starting price
+    pub fn clearing_price(&self, nft_info: &NftInfo) -> u64 {
+        match self.auction_type {
+            AuctionType::Sealed { second_price: true, .. } =>
+                self.runner_up_price
+                    .max(nft_info.reserve_price.unwrap_or(0))
+                    .max(nft_info.starting_price)
+                    .min(nft_info.current_price),
+            _ => nft_info.current_price,
+        }
+    }
+
+    /// Current price of a Dutch auction, dropping `price_drop` every `drop_interval` down to the floor
+    pub fn dutch_price(&self, now: i64) -> Option<u64> {
+        match self.auction_type {
+            AuctionType::Dutch { start_price, floor_price, price_drop, drop_interval } => {
+                let elapsed = now.saturating_sub(self.auction_start_time).max(0) as u64;
+                let drops = elapsed / (drop_interval as u64);
+                let discount = drops.saturating_mul(price_drop);
+
+                Some(start_price.saturating_sub(discount).max(floor_price))
+            }
+            AuctionType::English | AuctionType::Sealed { .. } => None,
+        }
+    }
+}
+
+/// `AuctionState` layout before NFTs were scoped by library, read once by `migrate_nft`
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct LegacyAuctionState {
     pub nft_mint: Pubkey,
     pub seller: Pubkey,
     pub auction_end_time: i64,
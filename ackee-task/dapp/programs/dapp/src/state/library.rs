@@ -1,6 +1,45 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::{ MAX_LIBRARY_DELEGATES, MAX_LIBRARY_FIELD_LEN };
+use crate::enums::ErrorCode;
+
+#[derive(InitSpace)]
+#[account]
 pub struct Library {
+    pub authority: Pubkey,
+    #[max_len(MAX_LIBRARY_DELEGATES)]
+    pub delegates: Vec<Pubkey>,
+    #[max_len(32)]
     pub name: String,
+    #[max_len(MAX_LIBRARY_FIELD_LEN)]
     pub description: String,
+    #[max_len(MAX_LIBRARY_FIELD_LEN)]
     pub url: String,
+    #[max_len(MAX_LIBRARY_FIELD_LEN)]
     pub icon: String,
+    pub bump: u8,
+}
+
+impl Library {
+    /// The authority and its delegates are the only wallets allowed to mint into the library
+    pub fn can_mint(&self, minter: &Pubkey) -> bool {
+        self.authority == *minter || self.delegates.contains(minter)
+    }
+
+    pub fn validate_field(value: &str) -> Result<()> {
+        require!(value.len() <= (MAX_LIBRARY_FIELD_LEN as usize), ErrorCode::LibraryFieldTooLong);
+
+        Ok(())
+    }
+
+    pub fn set_delegates(&mut self, delegates: Vec<Pubkey>) -> Result<()> {
+        require!(
+            delegates.len() <= (MAX_LIBRARY_DELEGATES as usize),
+            ErrorCode::TooManyLibraryDelegates
+        );
+
+        self.delegates = delegates;
+
+        Ok(())
+    }
 }
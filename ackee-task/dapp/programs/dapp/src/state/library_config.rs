@@ -0,0 +1,34 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::MAX_BASIS_POINTS;
+use crate::enums::ErrorCode;
+
+#[account]
+#[derive(InitSpace)]
+pub struct LibraryConfig {
+    pub seller_fee_basis_points: u16,
+    pub protocol_fee_basis_points: u16,
+    pub protocol_fee_account: Option<Pubkey>,
+    pub bump: u8,
+}
+
+impl LibraryConfig {
+    pub fn validate_fees(
+        seller_fee_basis_points: u16,
+        protocol_fee_basis_points: u16,
+        protocol_fee_account: Option<Pubkey>
+    ) -> Result<()> {
+        require!(
+            seller_fee_basis_points
+                .checked_add(protocol_fee_basis_points)
+                .is_some_and(|total| total <= MAX_BASIS_POINTS),
+            ErrorCode::InvalidFeeBasisPoints
+        );
+        require!(
+            protocol_fee_basis_points == 0 || protocol_fee_account.is_some(),
+            ErrorCode::InvalidProtocolFeeAccount
+        );
+
+        Ok(())
+    }
+}
@@ -6,3 +6,15 @@ pub use auction_state::*;
 
 pub mod escrow_bidder_state;
 pub use escrow_bidder_state::*;
+
+pub mod library;
+pub use library::*;
+
+pub mod library_config;
+pub use library_config::*;
+
+pub mod pending_refund;
+pub use pending_refund::*;
+
+pub mod sealed_bid;
+pub use sealed_bid::*;
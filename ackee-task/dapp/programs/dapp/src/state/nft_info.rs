@@ -1,5 +1,7 @@
 use anchor_lang::prelude::*;
 
+use crate::enums::ErrorCode;
+
 #[derive(InitSpace)]
 #[account]
 pub struct NftInfo {
@@ -7,9 +9,52 @@ pub struct NftInfo {
     pub starting_price: u64,
     pub current_bidder: Pubkey,
     pub bid_step: u64,
+    pub reserve_price: Option<u64>,
+    pub buy_now_price: Option<u64>,
+    pub payment_mint: Option<Pubkey>,
     pub bump: u8,
     #[max_len(32)]
     pub library_name: String,
     #[max_len(32)]
     pub name: String,
+    pub nft_mint: Pubkey,
+    // Bumped by `relist` and `cancel_auction`, commitments from an earlier round cannot be revealed.
+    // Kept here rather than in `AuctionState`, which a cancelled auction closes.
+    pub round: u32,
+}
+
+impl NftInfo {
+    /// The auction ended unsold without bids or with the top bid below the reserve
+    pub fn is_unsold(&self, seller: &Pubkey) -> bool {
+        let reserve_met = match self.reserve_price {
+            Some(reserve_price) => self.current_price >= reserve_price,
+            None => true,
+        };
+
+        self.current_bidder == *seller || !reserve_met
+    }
+
+    /// Hands the current_bidder slot back to the seller, the old leader can then withdraw
+    pub fn reset_bids(&mut self, seller: Pubkey) {
+        self.current_bidder = seller;
+        self.current_price = self.starting_price;
+    }
+
+    pub fn next_round(&mut self) -> Result<()> {
+        self.round = self.round.checked_add(1).ok_or(ErrorCode::ArithmeticOverflow)?;
+
+        Ok(())
+    }
+}
+
+/// `NftInfo` layout before NFTs were scoped by library, read once by `migrate_nft`
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct LegacyNftInfo {
+    pub current_price: u64,
+    pub starting_price: u64,
+    pub current_bidder: Pubkey,
+    pub bid_step: u64,
+    pub bump: u8,
+    pub library_name: String,
+    pub name: String,
 }
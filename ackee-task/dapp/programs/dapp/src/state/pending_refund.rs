@@ -0,0 +1,22 @@
+use anchor_lang::prelude::*;
+
+/// Funds a bidder has locked in the NFT escrow, withdrawable with `withdraw_refund` once outbid
+#[derive(InitSpace)]
+#[account]
+pub struct PendingRefund {
+    pub bidder: Pubkey,
+    pub payment_mint: Option<Pubkey>,
+    pub amount: u64,
+    pub bump: u8,
+}
+
+impl PendingRefund {
+    /// Fills in a record freshly created by `init_if_needed`, existing records are left as they are
+    pub fn open(&mut self, bidder: Pubkey, payment_mint: Option<Pubkey>, bump: u8) {
+        if self.bidder == Pubkey::default() {
+            self.bidder = bidder;
+            self.payment_mint = payment_mint;
+            self.bump = bump;
+        }
+    }
+}
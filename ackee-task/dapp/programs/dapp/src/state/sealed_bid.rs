@@ -0,0 +1,20 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::hash::hashv;
+
+/// Commitment of a bidder in a sealed auction, the deposit itself sits in their `PendingRefund`
+#[derive(InitSpace)]
+#[account]
+pub struct SealedBid {
+    pub bidder: Pubkey,
+    pub commitment: [u8; 32],
+    pub bump: u8,
+    // `NftInfo.round` the commitment was made in
+    pub round: u32,
+}
+
+impl SealedBid {
+    /// sha256 of the little endian amount, the salt and the bidder, so commitments cannot be copied
+    pub fn commitment(amount: u64, salt: &[u8; 32], bidder: &Pubkey) -> [u8; 32] {
+        hashv(&[&amount.to_le_bytes(), salt, bidder.as_ref()]).to_bytes()
+    }
+}
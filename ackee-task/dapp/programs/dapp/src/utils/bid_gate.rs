@@ -0,0 +1,58 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::hash::hashv;
+use anchor_spl::{ metadata::MetadataAccount, token_interface::TokenAccount };
+
+use crate::enums::{ AuctionGate, ErrorCode };
+
+/// Whatever the bidder supplied to get past the gate, only the part the gate asks for is read
+pub struct GateProof<'a, 'info> {
+    pub token_account: Option<&'a InterfaceAccount<'info, TokenAccount>>,
+    pub metadata: Option<&'a MetadataAccount>,
+    pub merkle_proof: Option<Vec<[u8; 32]>>,
+}
+
+pub fn _check_gate(gate: &Option<AuctionGate>, bidder: &Pubkey, proof: GateProof) -> Result<()> {
+    let Some(gate) = gate else {
+        return Ok(());
+    };
+
+    let passed = match gate {
+        AuctionGate::TokenHolder { mint } =>
+            proof.token_account.is_some_and(|token_account| {
+                _holds(token_account, bidder) && token_account.mint == *mint
+            }),
+        AuctionGate::CollectionHolder { collection } =>
+            match (proof.token_account, proof.metadata) {
+                (Some(token_account), Some(metadata)) =>
+                    _holds(token_account, bidder) &&
+                        metadata.mint == token_account.mint &&
+                        metadata.collection
+                            .as_ref()
+                            .is_some_and(|c| c.verified && c.key == *collection),
+                _ => false,
+            }
+        AuctionGate::Allowlist { merkle_root } =>
+            proof.merkle_proof.is_some_and(|merkle_proof| {
+                _merkle_root(hashv(&[bidder.as_ref()]).to_bytes(), &merkle_proof) == *merkle_root
+            }),
+    };
+
+    require!(passed, ErrorCode::GateNotSatisfied);
+
+    Ok(())
+}
+
+fn _holds(token_account: &InterfaceAccount<TokenAccount>, bidder: &Pubkey) -> bool {
+    token_account.owner == *bidder && token_account.amount > 0
+}
+
+/// Folds the proof into the leaf, each pair is hashed smaller node first
+fn _merkle_root(leaf: [u8; 32], merkle_proof: &[[u8; 32]]) -> [u8; 32] {
+    merkle_proof.iter().fold(leaf, |node, sibling| {
+        if node <= *sibling {
+            hashv(&[&node, sibling]).to_bytes()
+        } else {
+            hashv(&[sibling, &node]).to_bytes()
+        }
+    })
+}
@@ -0,0 +1,242 @@
+use anchor_lang::{ prelude::*, system_program };
+use anchor_lang::system_program::{ transfer, Transfer };
+use anchor_spl::{
+    associated_token::get_associated_token_address_with_program_id,
+    token_interface::{
+        Mint,
+        TokenAccount,
+        TokenInterface,
+        transfer_checked,
+        TransferChecked,
+        close_account,
+        CloseAccount,
+    },
+};
+
+use crate::enums::ErrorCode;
+use crate::state::{ EscrowBidder, PendingRefund };
+use crate::constants::*;
+
+/// Token side of an auction priced in an SPL mint, held in an ATA owned by the `EscrowBidder` PDA
+pub struct TokenPayment<'a, 'info> {
+    pub payment_mint: &'a InterfaceAccount<'info, Mint>,
+    pub escrow_payment_account: &'a InterfaceAccount<'info, TokenAccount>,
+    pub token_program: &'a Interface<'info, TokenInterface>,
+}
+
+/// Checks the optional payment accounts against the auction payment mint, `None` means native lamports
+pub fn _token_payment<'a, 'info>(
+    expected_mint: Option<Pubkey>,
+    nft_bidder_escrow: &Pubkey,
+    payment_mint: &'a Option<InterfaceAccount<'info, Mint>>,
+    escrow_payment_account: &'a Option<InterfaceAccount<'info, TokenAccount>>,
+    token_program: &'a Interface<'info, TokenInterface>
+) -> Result<Option<TokenPayment<'a, 'info>>> {
+    match (expected_mint, payment_mint, escrow_payment_account) {
+        (None, None, None) => Ok(None),
+        (Some(expected_mint), Some(payment_mint), Some(escrow_payment_account)) => {
+            require_keys_eq!(payment_mint.key(), expected_mint, ErrorCode::InvalidPaymentMint);
+            require_keys_eq!(
+                escrow_payment_account.key(),
+                get_associated_token_address_with_program_id(
+                    nft_bidder_escrow,
+                    &expected_mint,
+                    &token_program.key()
+                ),
+                ErrorCode::InvalidPaymentAccount
+            );
+
+            Ok(
+                Some(TokenPayment {
+                    payment_mint,
+                    escrow_payment_account,
+                    token_program,
+                })
+            )
+        }
+        _ => err!(ErrorCode::InvalidPaymentMint),
+    }
+}
+
+pub fn _deposit_to_escrow<'info>(
+    payer: &AccountInfo<'info>,
+    payer_payment_account: Option<&InterfaceAccount<'info, TokenAccount>>,
+    nft_bidder_escrow: &AccountInfo<'info>,
+    token_payment: &Option<TokenPayment<'_, 'info>>,
+    system_program: &Program<'info, System>,
+    amount: u64
+) -> Result<()> {
+    match token_payment {
+        Some(token_payment) => {
+            let payer_payment_account = payer_payment_account.ok_or(
+                ErrorCode::InvalidPaymentAccount
+            )?;
+
+            transfer_checked(
+                CpiContext::new(token_payment.token_program.to_account_info(), TransferChecked {
+                    from: payer_payment_account.to_account_info(),
+                    to: token_payment.escrow_payment_account.to_account_info(),
+                    authority: payer.to_account_info(),
+                    mint: token_payment.payment_mint.to_account_info(),
+                }),
+                amount,
+                token_payment.payment_mint.decimals
+            )
+        }
+        None =>
+            transfer(
+                CpiContext::new(system_program.to_account_info(), Transfer {
+                    from: payer.to_account_info(),
+                    to: nft_bidder_escrow.to_account_info(),
+                }),
+                amount
+            ),
+    }
+}
+
+/// Pays out of the escrow PDA derived from `library_name` and `name`
+pub fn _pay_from_escrow<'info>(
+    nft_bidder_escrow: &Account<'info, EscrowBidder>,
+    library_name: &str,
+    name: &str,
+    recipient: &AccountInfo<'info>,
+    recipient_payment_account: Option<&InterfaceAccount<'info, TokenAccount>>,
+    token_payment: &Option<TokenPayment<'_, 'info>>,
+    amount: u64
+) -> Result<()> {
+    match token_payment {
+        Some(token_payment) => {
+            let recipient_payment_account = recipient_payment_account.ok_or(
+                ErrorCode::InvalidPaymentAccount
+            )?;
+
+            require_keys_eq!(
+                recipient_payment_account.owner,
+                recipient.key(),
+                ErrorCode::InvalidPaymentAccount
+            );
+            require_keys_eq!(
+                recipient_payment_account.mint,
+                token_payment.payment_mint.key(),
+                ErrorCode::InvalidPaymentAccount
+            );
+            let signer_seeds: &[&[&[u8]]] = &[
+                &[
+                    BIDDER_ESCROW_SEEDS,
+                    library_name.as_bytes(),
+                    name.as_bytes(),
+                    &[nft_bidder_escrow.bump],
+                ],
+            ];
+
+            transfer_checked(
+                CpiContext::new_with_signer(
+                    token_payment.token_program.to_account_info(),
+                    TransferChecked {
+                        from: token_payment.escrow_payment_account.to_account_info(),
+                        to: recipient_payment_account.to_account_info(),
+                        authority: nft_bidder_escrow.to_account_info(),
+                        mint: token_payment.payment_mint.to_account_info(),
+                    },
+                    signer_seeds
+                ),
+                amount,
+                token_payment.payment_mint.decimals
+            )
+        }
+        None => {
+            require!(recipient.owner == &system_program::ID, ErrorCode::InvalidAccountOwner);
+            require!(nft_bidder_escrow.get_lamports() >= amount, ErrorCode::InsufficientFunds);
+
+            **nft_bidder_escrow.to_account_info().try_borrow_mut_lamports()? -= amount;
+            **recipient.try_borrow_mut_lamports()? += amount;
+
+            Ok(())
+        }
+    }
+}
+
+/// Returns the escrow payment token account rent once the auction no longer needs it
+pub fn _close_escrow_payment_account<'info>(
+    nft_bidder_escrow: &Account<'info, EscrowBidder>,
+    library_name: &str,
+    name: &str,
+    token_payment: &Option<TokenPayment<'_, 'info>>,
+    destination: &AccountInfo<'info>
+) -> Result<()> {
+    let Some(token_payment) = token_payment else {
+        return Ok(());
+    };
+
+    let signer_seeds: &[&[&[u8]]] = &[
+        &[
+            BIDDER_ESCROW_SEEDS,
+            library_name.as_bytes(),
+            name.as_bytes(),
+            &[nft_bidder_escrow.bump],
+        ],
+    ];
+
+    close_account(
+        CpiContext::new_with_signer(
+            token_payment.token_program.to_account_info(),
+            CloseAccount {
+                account: token_payment.escrow_payment_account.to_account_info(),
+                destination: destination.to_account_info(),
+                authority: nft_bidder_escrow.to_account_info(),
+            },
+            signer_seeds
+        )
+    )
+}
+
+/// Deposits only what the bidder does not already have locked in the escrow
+pub fn _lock_bid<'info>(
+    payer: &AccountInfo<'info>,
+    payer_payment_account: Option<&InterfaceAccount<'info, TokenAccount>>,
+    nft_bidder_escrow: &AccountInfo<'info>,
+    pending_refund: &mut PendingRefund,
+    token_payment: &Option<TokenPayment<'_, 'info>>,
+    system_program: &Program<'info, System>,
+    amount: u64
+) -> Result<()> {
+    let deposit_amount = amount.saturating_sub(pending_refund.amount);
+
+    if deposit_amount > 0 {
+        _deposit_to_escrow(
+            payer,
+            payer_payment_account,
+            nft_bidder_escrow,
+            token_payment,
+            system_program,
+            deposit_amount
+        )?;
+    }
+
+    pending_refund.amount = pending_refund.amount
+        .checked_add(deposit_amount)
+        .ok_or(ErrorCode::ArithmeticOverflow)?;
+
+    Ok(())
+}
+
+/// Nothing but rent is left once every locked bid has been paid out or withdrawn
+pub fn _escrow_is_drained<'info>(
+    nft_bidder_escrow: &Account<'info, EscrowBidder>,
+    token_payment: &Option<TokenPayment<'_, 'info>>
+) -> Result<bool> {
+    match token_payment {
+        Some(token_payment) => {
+            let mut escrow_payment_account = token_payment.escrow_payment_account.clone();
+            escrow_payment_account.reload()?;
+
+            Ok(escrow_payment_account.amount == 0)
+        }
+        None => {
+            let escrow_info = nft_bidder_escrow.to_account_info();
+            let rent = Rent::get()?.minimum_balance(escrow_info.data_len());
+
+            Ok(escrow_info.lamports() <= rent)
+        }
+    }
+}
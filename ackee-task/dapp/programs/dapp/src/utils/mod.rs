@@ -0,0 +1,14 @@
+pub mod escrow_payments;
+pub use escrow_payments::*;
+
+pub mod settlement_fees;
+pub use settlement_fees::*;
+
+pub mod nft_metadata;
+pub use nft_metadata::*;
+
+pub mod bid_gate;
+pub use bid_gate::*;
+
+pub mod unsold_nft;
+pub use unsold_nft::*;
@@ -0,0 +1,53 @@
+use anchor_lang::prelude::*;
+use anchor_spl::metadata::mpl_token_metadata::types::Creator;
+
+use crate::enums::ErrorCode;
+use crate::constants::*;
+
+/// Creator entry accepted by `init_library` and `mint_nft`, verified later by the creator signing
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq)]
+pub struct NftCreator {
+    pub address: Pubkey,
+    pub share: u8,
+}
+
+/// Checks the fields against the Metaplex limits, the symbol falls back to `SYMBOL`
+pub fn _validate_metadata(name: &str, symbol: Option<String>, uri: &str) -> Result<String> {
+    let symbol = symbol.unwrap_or_else(|| SYMBOL.to_string());
+
+    require!(name.len() <= (MAX_NAME_LEN as usize), ErrorCode::MetadataFieldTooLong);
+    require!(symbol.len() <= (MAX_SYMBOL_LEN as usize), ErrorCode::MetadataFieldTooLong);
+    require!(!uri.is_empty() && uri.len() <= (MAX_URI_LEN as usize), ErrorCode::MetadataFieldTooLong);
+
+    Ok(symbol)
+}
+
+/// Turns the creators argument into Metaplex creators, `default_creator` gets the full share when none are given.
+/// Only the signing update authority may start out verified, everyone else has to sign the metadata.
+pub fn _metadata_creators(
+    creators: Option<Vec<NftCreator>>,
+    default_creator: Pubkey,
+    update_authority: Option<Pubkey>
+) -> Result<Vec<Creator>> {
+    let creators = creators.unwrap_or_else(|| vec![NftCreator { address: default_creator, share: 100 }]);
+
+    require!(
+        !creators.is_empty() && creators.len() <= (MAX_CREATORS as usize),
+        ErrorCode::InvalidCreatorShares
+    );
+    require!(
+        creators.iter().map(|creator| creator.share as u16).sum::<u16>() == 100,
+        ErrorCode::InvalidCreatorShares
+    );
+
+    Ok(
+        creators
+            .into_iter()
+            .map(|creator| Creator {
+                address: creator.address,
+                verified: Some(creator.address) == update_authority,
+                share: creator.share,
+            })
+            .collect()
+    )
+}
@@ -0,0 +1,132 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{ metadata::MetadataAccount, token_interface::TokenAccount };
+
+use crate::enums::ErrorCode;
+use crate::state::{ EscrowBidder, LibraryConfig, NftInfo };
+use crate::constants::*;
+use crate::utils::{ TokenPayment, _pay_from_escrow };
+
+pub struct FeeAccounts<'a, 'info> {
+    pub library_config: &'a LibraryConfig,
+    pub nft_metadata: &'a MetadataAccount,
+    pub protocol_fee_account: Option<&'a AccountInfo<'info>>,
+    pub protocol_fee_payment_account: Option<&'a InterfaceAccount<'info, TokenAccount>>,
+    // One wallet per verified creator, followed by its payment token account for SPL auctions
+    pub creator_accounts: &'info [AccountInfo<'info>],
+}
+
+pub struct SettlementFees {
+    pub royalty_amount: u64,
+    pub protocol_fee: u64,
+}
+
+pub fn _basis_points_of(amount: u64, basis_points: u16) -> Result<u64> {
+    let value = ((amount as u128) * (basis_points as u128)) / (MAX_BASIS_POINTS as u128);
+
+    u64::try_from(value).map_err(|_| error!(ErrorCode::InvalidFeeBasisPoints))
+}
+
+/// A lamport payout that leaves the wallet below rent exemption fails the whole settlement, such
+/// dust is left to the seller instead
+fn _can_receive(
+    recipient: &AccountInfo,
+    token_payment: &Option<TokenPayment<'_, '_>>,
+    amount: u64
+) -> Result<bool> {
+    if token_payment.is_some() {
+        return Ok(true);
+    }
+
+    let balance = recipient.lamports().checked_add(amount).ok_or(ErrorCode::ArithmeticOverflow)?;
+
+    Ok(Rent::get()?.is_exempt(balance, recipient.data_len()))
+}
+
+/// Pays the protocol fee and the creator royalties out of the escrow, the seller gets what is left
+///
+/// Royalties are capped at what the protocol fee leaves of `MAX_BASIS_POINTS`, the metadata
+/// royalty can be raised after the library fees were checked.
+pub fn _pay_settlement_fees<'info>(
+    nft_bidder_escrow: &Account<'info, EscrowBidder>,
+    nft_info: &NftInfo,
+    token_payment: &Option<TokenPayment<'_, 'info>>,
+    fee_accounts: &FeeAccounts<'_, 'info>,
+    final_price: u64
+) -> Result<SettlementFees> {
+    let library_config = fee_accounts.library_config;
+    let mut protocol_fee = _basis_points_of(final_price, library_config.protocol_fee_basis_points)?;
+
+    if protocol_fee > 0 {
+        let protocol_fee_account = fee_accounts.protocol_fee_account.ok_or(
+            ErrorCode::InvalidProtocolFeeAccount
+        )?;
+
+        require!(
+            library_config.protocol_fee_account == Some(protocol_fee_account.key()),
+            ErrorCode::InvalidProtocolFeeAccount
+        );
+
+        if _can_receive(protocol_fee_account, token_payment, protocol_fee)? {
+            _pay_from_escrow(
+                nft_bidder_escrow,
+                &nft_info.library_name,
+                &nft_info.name,
+                protocol_fee_account,
+                fee_accounts.protocol_fee_payment_account,
+                token_payment,
+                protocol_fee
+            )?;
+        } else {
+            protocol_fee = 0;
+        }
+    }
+
+    let royalty_basis_points = fee_accounts.nft_metadata.seller_fee_basis_points.min(
+        MAX_BASIS_POINTS.saturating_sub(library_config.protocol_fee_basis_points)
+    );
+    let royalty = _basis_points_of(final_price, royalty_basis_points)?;
+    let mut royalty_amount = 0;
+
+    let verified_creators = fee_accounts.nft_metadata.creators
+        .iter()
+        .flatten()
+        .filter(|creator| creator.verified && creator.share > 0);
+
+    let accounts_per_creator = if token_payment.is_some() { 2 } else { 1 };
+    let mut creator_accounts = fee_accounts.creator_accounts.chunks(accounts_per_creator);
+
+    for creator in verified_creators {
+        let amount = ((royalty as u128) * (creator.share as u128)) / 100;
+        let amount = amount as u64;
+
+        let creator_account = creator_accounts.next().ok_or(ErrorCode::InvalidCreatorAccount)?;
+        require!(creator_account.len() == accounts_per_creator, ErrorCode::InvalidCreatorAccount);
+        require_keys_eq!(creator_account[0].key(), creator.address, ErrorCode::InvalidCreatorAccount);
+
+        if amount == 0 || !_can_receive(&creator_account[0], token_payment, amount)? {
+            continue;
+        }
+
+        let creator_payment_account = match token_payment {
+            Some(_) => Some(InterfaceAccount::<TokenAccount>::try_from(&creator_account[1])?),
+            None => None,
+        };
+
+        _pay_from_escrow(
+            nft_bidder_escrow,
+            &nft_info.library_name,
+            &nft_info.name,
+            &creator_account[0],
+            creator_payment_account.as_ref(),
+            token_payment,
+            amount
+        )?;
+
+        royalty_amount += amount;
+    }
+
+    Ok(SettlementFees {
+        royalty_amount,
+        protocol_fee,
+    })
+}
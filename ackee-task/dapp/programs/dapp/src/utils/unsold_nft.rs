@@ -0,0 +1,79 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{
+    Mint,
+    TokenAccount,
+    TokenInterface,
+    transfer_checked,
+    TransferChecked,
+    close_account,
+    CloseAccount,
+};
+
+use crate::state::EscrowBidder;
+use crate::constants::*;
+use crate::utils::{ TokenPayment, _close_escrow_payment_account, _escrow_is_drained };
+
+pub struct UnsoldNftAccounts<'a, 'info> {
+    pub nft_bidder_escrow: &'a Account<'info, EscrowBidder>,
+    pub rent_payer: &'a AccountInfo<'info>,
+    pub nft_mint: &'a InterfaceAccount<'info, Mint>,
+    pub from_token_account: &'a InterfaceAccount<'info, TokenAccount>,
+    pub to_token_account: &'a InterfaceAccount<'info, TokenAccount>,
+    pub token_program: &'a Interface<'info, TokenInterface>,
+    pub token_payment: Option<TokenPayment<'a, 'info>>,
+}
+
+/// Moves an NFT nobody won out of the escrow back to the seller
+pub fn _return_unsold_nft(accounts: UnsoldNftAccounts, library_name: &str, name: &str) -> Result<()> {
+    let nft_bidder_escrow = accounts.nft_bidder_escrow;
+    let signer_seeds: &[&[&[u8]]] = &[
+        &[
+            BIDDER_ESCROW_SEEDS,
+            library_name.as_bytes(),
+            name.as_bytes(),
+            &[nft_bidder_escrow.bump],
+        ],
+    ];
+
+    transfer_checked(
+        CpiContext::new_with_signer(
+            accounts.token_program.to_account_info(),
+            TransferChecked {
+                from: accounts.from_token_account.to_account_info(),
+                to: accounts.to_token_account.to_account_info(),
+                authority: nft_bidder_escrow.to_account_info(),
+                mint: accounts.nft_mint.to_account_info(),
+            },
+            signer_seeds
+        ),
+        1,
+        0
+    )?;
+
+    close_account(
+        CpiContext::new_with_signer(
+            accounts.token_program.to_account_info(),
+            CloseAccount {
+                account: accounts.from_token_account.to_account_info(),
+                destination: accounts.rent_payer.to_account_info(),
+                authority: nft_bidder_escrow.to_account_info(),
+            },
+            signer_seeds
+        )
+    )?;
+
+    // Bids still locked in the escrow keep it open until the last `withdraw_refund`
+    if _escrow_is_drained(nft_bidder_escrow, &accounts.token_payment)? {
+        _close_escrow_payment_account(
+            nft_bidder_escrow,
+            library_name,
+            name,
+            &accounts.token_payment,
+            accounts.rent_payer
+        )?;
+
+        nft_bidder_escrow.close(accounts.rent_payer.to_account_info())?;
+    }
+
+    Ok(())
+}
@@ -0,0 +1,841 @@
+use std::{ fs, path::Path };
+
+use anchor_lang::{
+    prelude::Pubkey,
+    solana_program::{ program_option::COption, program_pack::Pack },
+    AccountSerialize,
+    AnchorSerialize,
+    Discriminator,
+};
+use anchor_spl::token::spl_token;
+use dapp::{
+    constants::*,
+    enums::ErrorCode,
+    AuctionGate,
+    AuctionType,
+    EscrowBidder,
+    LegacyNftInfo,
+    NftCreator,
+    NftInfo,
+};
+use solana_keypair::Keypair;
+use solana_signer::Signer;
+
+use crate::harness::*;
+
+const SEALED: AuctionType = AuctionType::Sealed { reveal_window: 600, second_price: false };
+
+/// A library with one English auction running on "Dune"
+fn english() -> (TestEnv, Library, Lot) {
+    auction(MintArgs::default(), AuctionType::English)
+}
+
+fn auction(args: MintArgs, auction_type: AuctionType) -> (TestEnv, Library, Lot) {
+    let mut env = TestEnv::new();
+    let library = env.library("Books");
+    let lot = env.auction(&library, "Dune", args, auction_type);
+
+    (env, library, lot)
+}
+
+/// A library with "Dune" minted but no auction started yet
+fn minted() -> (TestEnv, Library, Lot) {
+    let mut env = TestEnv::new();
+    let library = env.library("Books");
+    let lot = library.lot("Dune");
+
+    env.ok(library.mint("Dune", MintArgs::default()), &[&library.authority]);
+
+    (env, library, lot)
+}
+
+/// An English auction on "Dune" led by the returned bidder
+fn with_bid() -> (TestEnv, Library, Lot, Keypair) {
+    let (mut env, library, lot) = english();
+    let alice = env.wallet();
+
+    env.ok(lot.bid(&alice.pubkey(), PRICE), &[&alice]);
+
+    (env, library, lot, alice)
+}
+
+fn mint_error(args: MintArgs, code: ErrorCode) {
+    let mut env = TestEnv::new();
+    let library = env.library("Books");
+
+    expect_error(env.send(library.mint("Dune", args), &[&library.authority]), code);
+}
+
+fn init_library_error(
+    seller_fee_basis_points: u16,
+    protocol_fee_basis_points: u16,
+    protocol_fee_account: Option<Pubkey>,
+    code: ErrorCode
+) {
+    let mut env = TestEnv::new();
+    let library = Library {
+        name: "Books".to_string(),
+        authority: env.wallet(),
+        protocol_fee_account: Pubkey::new_unique(),
+    };
+    let init = library.init(seller_fee_basis_points, protocol_fee_basis_points, protocol_fee_account);
+
+    expect_error(env.send(init, &[&library.authority]), code);
+}
+
+fn start_error(auction_type: AuctionType, extension_window: i64, end_time: i64, code: ErrorCode) {
+    let (mut env, library, lot) = minted();
+    let start = lot.start(None, end_time, extension_window, auction_type, None);
+
+    expect_error(env.send(start, &[&library.authority]), code);
+}
+
+#[test]
+fn auction_ended() {
+    let (mut env, library, lot) = english();
+
+    env.warp(START + DURATION + 1);
+
+    expect_error(
+        env.send(lot.cancel(&library.authority.pubkey()), &[&library.authority]),
+        ErrorCode::AuctionEnded
+    );
+}
+
+#[test]
+fn bid_too_low() {
+    let (mut env, _library, lot, _alice) = with_bid();
+    let bob = env.wallet();
+
+    expect_error(env.send(lot.bid(&bob.pubkey(), PRICE + STEP - 1), &[&bob]), ErrorCode::BidTooLow);
+}
+
+#[test]
+fn bid_below_starting_price() {
+    let (mut env, _library, lot) = english();
+    let alice = env.wallet();
+
+    expect_error(
+        env.send(lot.bid(&alice.pubkey(), PRICE - 1), &[&alice]),
+        ErrorCode::BidBelowStartingPrice
+    );
+}
+
+#[test]
+fn invalid_highest_bidder() {
+    let (mut env, _library, lot, _alice) = with_bid();
+    let bob = env.wallet();
+
+    env.warp(START + DURATION + 1);
+
+    let mut accounts = lot.settle_accounts(&bob.pubkey(), &bob.pubkey());
+    accounts.winner_refund = None;
+
+    expect_error(
+        env.send(lot.settle_with(accounts, &lot.creator_accounts()), &[&bob]),
+        ErrorCode::InvalidHighestBidder
+    );
+}
+
+#[test]
+fn auction_time_expired() {
+    let (mut env, _library, lot) = english();
+    let alice = env.wallet();
+
+    env.warp(START + DURATION);
+
+    expect_error(
+        env.send(lot.bid(&alice.pubkey(), PRICE), &[&alice]),
+        ErrorCode::AuctionTimeExpired
+    );
+}
+
+#[test]
+fn auction_still_active() {
+    let (mut env, library, lot) = english();
+
+    expect_error(
+        env.send(lot.transfer(&lot.seller), &[&library.authority]),
+        ErrorCode::AuctionStillActive
+    );
+}
+
+#[test]
+fn time_set_is_not_valid() {
+    start_error(AuctionType::English, 0, START - 1, ErrorCode::TimeSetIsNotValid);
+}
+
+#[test]
+fn invalid_extension_window() {
+    start_error(AuctionType::English, -1, START + DURATION, ErrorCode::InvalidExtensionWindow);
+}
+
+#[test]
+fn invalid_dutch_auction() {
+    let dutch = AuctionType::Dutch {
+        start_price: PRICE,
+        floor_price: PRICE,
+        price_drop: 1,
+        drop_interval: 1,
+    };
+
+    start_error(dutch, 0, START + DURATION, ErrorCode::InvalidDutchAuction);
+}
+
+#[test]
+fn reserve_price_not_met() {
+    let dutch = AuctionType::Dutch {
+        start_price: 3 * PRICE,
+        floor_price: PRICE,
+        price_drop: PRICE,
+        drop_interval: 60,
+    };
+    let args = MintArgs { reserve_price: Some(2 * PRICE), ..MintArgs::default() };
+    let (mut env, _library, lot) = auction(args, dutch);
+    let alice = env.wallet();
+
+    // Two drops take the price under the reserve
+    env.warp(START + 120);
+
+    expect_error(
+        env.send(lot.bid(&alice.pubkey(), 3 * PRICE), &[&alice]),
+        ErrorCode::ReservePriceNotMet
+    );
+}
+
+#[test]
+fn library_account_not_found() {
+    let (mut env, library, lot) = minted();
+
+    env.set_token_amount(&library.account_address(), 0);
+
+    expect_error(
+        env.send(
+            lot.start(None, START + DURATION, 0, AuctionType::English, None),
+            &[&library.authority]
+        ),
+        ErrorCode::LibraryAccountNotFound
+    );
+}
+
+#[test]
+fn arithmetic_overflow() {
+    let args = MintArgs { bid_step: u64::MAX, ..MintArgs::default() };
+    let (mut env, _library, lot) = auction(args, AuctionType::English);
+    let (alice, bob) = (env.wallet(), env.wallet());
+
+    env.ok(lot.bid(&alice.pubkey(), PRICE), &[&alice]);
+
+    expect_error(
+        env.send(lot.bid(&bob.pubkey(), 2 * PRICE), &[&bob]),
+        ErrorCode::ArithmeticOverflow
+    );
+}
+
+#[test]
+fn insufficient_funds() {
+    let (mut env, _library, lot) = auction(MintArgs::default(), SEALED);
+    let alice = env.wallet();
+
+    env.ok(lot.commit(&alice.pubkey(), 2 * PRICE, [1; 32], PRICE), &[&alice]);
+    env.warp(START + DURATION);
+
+    expect_error(
+        env.send(lot.reveal(&alice.pubkey(), 2 * PRICE, [1; 32]), &[&alice]),
+        ErrorCode::InsufficientFunds
+    );
+}
+
+#[test]
+fn unauthorized_transfer() {
+    let (mut env, _library, lot) = minted();
+    let stranger = env.wallet();
+    let start = lot.start_by(
+        &stranger.pubkey(),
+        None,
+        START + DURATION,
+        0,
+        AuctionType::English,
+        None
+    );
+
+    expect_error(env.send(start, &[&stranger]), ErrorCode::UnauthorizedTransfer);
+}
+
+#[test]
+fn invalid_reserve_price() {
+    let args = MintArgs { reserve_price: Some(PRICE - 1), ..MintArgs::default() };
+
+    mint_error(args, ErrorCode::InvalidReservePrice);
+}
+
+#[test]
+fn invalid_buy_now_price() {
+    let args = MintArgs { buy_now_price: Some(PRICE), ..MintArgs::default() };
+
+    mint_error(args, ErrorCode::InvalidBuyNowPrice);
+}
+
+#[test]
+fn buy_now_not_available() {
+    let (mut env, _library, lot) = english();
+    let alice = env.wallet();
+
+    expect_error(env.send(lot.buy_now(&alice.pubkey()), &[&alice]), ErrorCode::BuyNowNotAvailable);
+}
+
+#[test]
+fn invalid_account_owner() {
+    let (mut env, library, lot, alice) = with_bid();
+
+    // Lamport payouts only go to system accounts
+    env.set_data(library.protocol_fee_account, spl_token::ID, vec![]);
+    env.warp(START + DURATION + 1);
+
+    expect_error(
+        env.send(lot.settle(&alice.pubkey(), &alice.pubkey()), &[&alice]),
+        ErrorCode::InvalidAccountOwner
+    );
+}
+
+#[test]
+fn unauthorized_cancel() {
+    let (mut env, _library, lot) = english();
+    let stranger = env.wallet();
+
+    expect_error(
+        env.send(lot.cancel(&stranger.pubkey()), &[&stranger]),
+        ErrorCode::UnauthorizedCancel
+    );
+}
+
+#[test]
+fn invalid_rent_payer() {
+    let (mut env, _library, lot, alice) = with_bid();
+
+    env.warp(START + DURATION + 1);
+
+    let mut accounts = lot.settle_accounts(&alice.pubkey(), &alice.pubkey());
+    accounts.rent_payer = alice.pubkey();
+
+    expect_error(
+        env.send(lot.settle_with(accounts, &lot.creator_accounts()), &[&alice]),
+        ErrorCode::InvalidRentPayer
+    );
+}
+
+#[test]
+fn invalid_payment_mint() {
+    let (mut env, _library, lot) = english();
+    let alice = env.wallet();
+
+    // Any mint and token account will do, the lot is priced in lamports
+    let mut accounts = lot.bid_accounts(&alice.pubkey());
+    accounts.payment_mint = Some(lot.nft_mint());
+    accounts.escrow_payment_account = Some(lot.escrow_nft_account());
+
+    expect_error(
+        env.send(lot.bid_with(accounts, PRICE, None), &[&alice]),
+        ErrorCode::InvalidPaymentMint
+    );
+}
+
+#[test]
+fn invalid_payment_account() {
+    let mut env = TestEnv::new();
+    let library = env.library("Books");
+    let mint = library.mint_with("Dune", MintArgs::default(), Some(library.mint_address()));
+
+    expect_error(env.send(mint, &[&library.authority]), ErrorCode::InvalidPaymentAccount);
+}
+
+#[test]
+fn invalid_fee_basis_points() {
+    init_library_error(9_000, 2_000, Some(Pubkey::new_unique()), ErrorCode::InvalidFeeBasisPoints);
+}
+
+#[test]
+fn invalid_protocol_fee_account() {
+    init_library_error(500, 100, None, ErrorCode::InvalidProtocolFeeAccount);
+}
+
+#[test]
+fn invalid_creator_account() {
+    let (mut env, _library, lot, alice) = with_bid();
+
+    env.warp(START + DURATION + 1);
+
+    let accounts = lot.settle_accounts(&alice.pubkey(), &alice.pubkey());
+
+    expect_error(
+        env.send(lot.settle_with(accounts, &[]), &[&alice]),
+        ErrorCode::InvalidCreatorAccount
+    );
+}
+
+#[test]
+fn invalid_legacy_account() {
+    let mut env = TestEnv::new();
+    let payer = env.wallet();
+    let name = "Dune";
+    let library_name = "Books";
+
+    let legacy_nft_info = pda(&[BUID_NFT_SEEDS, name.as_bytes()]);
+    let nft_mint = pda(&[name.as_bytes()]);
+    let (escrow, escrow_bump) = Pubkey::find_program_address(
+        &[BIDDER_ESCROW_SEEDS, library_name.as_bytes(), name.as_bytes()],
+        &dapp::ID
+    );
+
+    // A pre-migration NftInfo that claims to belong to another library
+    let mut legacy_data = NftInfo::DISCRIMINATOR.to_vec();
+    (LegacyNftInfo {
+        current_price: PRICE,
+        starting_price: PRICE,
+        current_bidder: payer.pubkey(),
+        bid_step: STEP,
+        bump: 0,
+        library_name: "Other".to_string(),
+        name: name.to_string(),
+    })
+        .serialize(&mut legacy_data)
+        .unwrap();
+    env.set_data(legacy_nft_info, dapp::ID, legacy_data);
+
+    let mut mint_data = vec![0; spl_token::state::Mint::LEN];
+    spl_token::state::Mint::pack(
+        spl_token::state::Mint {
+            mint_authority: COption::None,
+            supply: 1,
+            decimals: 0,
+            is_initialized: true,
+            freeze_authority: COption::None,
+        },
+        &mut mint_data
+    ).unwrap();
+    env.set_data(nft_mint, spl_token::ID, mint_data);
+
+    let mut escrow_data = Vec::new();
+    (EscrowBidder { payer: payer.pubkey(), bump: escrow_bump })
+        .try_serialize(&mut escrow_data)
+        .unwrap();
+    env.set_data(escrow, dapp::ID, escrow_data);
+
+    let migrate = instruction(
+        dapp::accounts::MigrateNft {
+            payer: payer.pubkey(),
+            legacy_nft_info,
+            legacy_auction_state: pda(&[AUCTION_STATE_SEEDS, name.as_bytes()]),
+            nft_info: pda(&[BUID_NFT_SEEDS, library_name.as_bytes(), name.as_bytes()]),
+            auction_state: None,
+            legacy_bidder: None,
+            bidder_refund: None,
+            nft_mint,
+            nft_bidder_escrow: escrow,
+            system_program: anchor_lang::system_program::ID,
+        },
+        dapp::instruction::MigrateNft {
+            name: name.to_string(),
+            library_name: library_name.to_string(),
+        },
+        &[]
+    );
+
+    expect_error(env.send(migrate, &[&payer]), ErrorCode::InvalidLegacyAccount);
+}
+
+#[test]
+fn unauthorized_migration() {
+    let mut env = TestEnv::new();
+    let (library, _) = env.legacy_library("Books", "Dune");
+    let (admin, mallory) = (env.wallet(), env.wallet());
+
+    env.set_upgrade_authority(&admin.pubkey());
+
+    // Legacy libraries have no owner on-chain, only the upgrade authority can assign one
+    expect_error(
+        env.send(library.migrate(&mallory.pubkey()), &[&mallory]),
+        ErrorCode::UnauthorizedMigration
+    );
+}
+
+#[test]
+fn invalid_previous_bidder() {
+    let mut env = TestEnv::new();
+    let (library, lot) = env.legacy_library("Books", "Dune");
+    let (admin, alice, mallory) = (env.wallet(), env.wallet(), env.wallet());
+
+    env.set_upgrade_authority(&admin.pubkey());
+    env.ok(library.migrate(&admin.pubkey()), &[&admin]);
+    env.legacy_auction(&lot, START + DURATION, Some((&alice.pubkey(), PRICE + STEP)));
+
+    // The leading legacy bid can only be carried over to the wallet that placed it
+    expect_error(
+        env.send(lot.migrate_with(true, Some(&mallory.pubkey())), &[&library.authority]),
+        ErrorCode::InvalidPreviousBidder
+    );
+}
+
+#[test]
+fn unauthorized_library_authority() {
+    let mut env = TestEnv::new();
+    let library = env.library("Books");
+    let stranger = env.wallet();
+    let update = library.update(&stranger.pubkey(), Some("Mine now".to_string()), None);
+
+    expect_error(env.send(update, &[&stranger]), ErrorCode::UnauthorizedLibraryAuthority);
+}
+
+#[test]
+fn too_many_library_delegates() {
+    let mut env = TestEnv::new();
+    let library = env.library("Books");
+    let delegates = (0..=MAX_LIBRARY_DELEGATES).map(|_| Pubkey::new_unique()).collect();
+    let update = library.update(&library.authority.pubkey(), None, Some(delegates));
+
+    expect_error(env.send(update, &[&library.authority]), ErrorCode::TooManyLibraryDelegates);
+}
+
+#[test]
+fn library_field_too_long() {
+    let mut env = TestEnv::new();
+    let library = env.library("Books");
+    let description = "a".repeat((MAX_LIBRARY_FIELD_LEN as usize) + 1);
+    let update = library.update(&library.authority.pubkey(), Some(description), None);
+
+    expect_error(env.send(update, &[&library.authority]), ErrorCode::LibraryFieldTooLong);
+}
+
+#[test]
+fn metadata_field_too_long() {
+    let args = MintArgs { uri: String::new(), ..MintArgs::default() };
+
+    mint_error(args, ErrorCode::MetadataFieldTooLong);
+}
+
+#[test]
+fn invalid_creator_shares() {
+    let creators = vec![NftCreator { address: Pubkey::new_unique(), share: 50 }];
+    let args = MintArgs { creators: Some(creators), ..MintArgs::default() };
+
+    mint_error(args, ErrorCode::InvalidCreatorShares);
+}
+
+#[test]
+fn invalid_batch_mint() {
+    let mut env = TestEnv::new();
+    let library = env.library("Books");
+
+    expect_error(
+        env.send(library.batch_mint(Vec::new()), &[&library.authority]),
+        ErrorCode::InvalidBatchMint
+    );
+}
+
+#[test]
+fn invalid_pending_refund() {
+    let (mut env, _library, lot, alice) = with_bid();
+
+    env.warp(START + DURATION + 1);
+
+    let mut accounts = lot.settle_accounts(&alice.pubkey(), &alice.pubkey());
+    accounts.winner_refund = None;
+
+    expect_error(
+        env.send(lot.settle_with(accounts, &lot.creator_accounts()), &[&alice]),
+        ErrorCode::InvalidPendingRefund
+    );
+}
+
+#[test]
+fn refund_locked() {
+    let (mut env, _library, lot, alice) = with_bid();
+
+    expect_error(
+        env.send(lot.withdraw_refund(&alice.pubkey()), &[&alice]),
+        ErrorCode::RefundLocked
+    );
+}
+
+#[test]
+fn auction_has_winner() {
+    let (mut env, library, lot, _alice) = with_bid();
+
+    env.warp(START + DURATION + 1);
+
+    expect_error(
+        env.send(lot.relist(env.now() + DURATION, None), &[&library.authority]),
+        ErrorCode::AuctionHasWinner
+    );
+}
+
+#[test]
+fn auction_not_started() {
+    let (mut env, library, lot) = minted();
+    let alice = env.wallet();
+
+    env.ok(
+        lot.start(Some(START + 600), START + DURATION, 0, AuctionType::English, None),
+        &[&library.authority]
+    );
+
+    expect_error(
+        env.send(lot.bid(&alice.pubkey(), PRICE), &[&alice]),
+        ErrorCode::AuctionNotStarted
+    );
+}
+
+#[test]
+fn auction_already_started() {
+    let (mut env, library, lot) = english();
+
+    expect_error(
+        env.send(lot.reschedule(None, START + 2 * DURATION), &[&library.authority]),
+        ErrorCode::AuctionAlreadyStarted
+    );
+}
+
+#[test]
+fn gate_not_satisfied() {
+    let (mut env, library, lot) = minted();
+    let alice = env.wallet();
+    let gate = AuctionGate::Allowlist { merkle_root: [1; 32] };
+
+    env.ok(
+        lot.start(None, START + DURATION, 0, AuctionType::English, Some(gate)),
+        &[&library.authority]
+    );
+
+    expect_error(
+        env.send(lot.bid(&alice.pubkey(), PRICE), &[&alice]),
+        ErrorCode::GateNotSatisfied
+    );
+}
+
+#[test]
+fn sealed_bids_only() {
+    let (mut env, _library, lot) = auction(MintArgs::default(), SEALED);
+    let alice = env.wallet();
+
+    expect_error(env.send(lot.bid(&alice.pubkey(), PRICE), &[&alice]), ErrorCode::SealedBidsOnly);
+}
+
+#[test]
+fn not_sealed_auction() {
+    let (mut env, _library, lot) = english();
+    let alice = env.wallet();
+
+    expect_error(
+        env.send(lot.commit(&alice.pubkey(), PRICE, [1; 32], PRICE), &[&alice]),
+        ErrorCode::NotSealedAuction
+    );
+}
+
+#[test]
+fn reveal_not_active() {
+    let (mut env, _library, lot) = auction(MintArgs::default(), SEALED);
+    let alice = env.wallet();
+
+    env.ok(lot.commit(&alice.pubkey(), PRICE, [1; 32], PRICE), &[&alice]);
+
+    expect_error(
+        env.send(lot.reveal(&alice.pubkey(), PRICE, [1; 32]), &[&alice]),
+        ErrorCode::RevealNotActive
+    );
+}
+
+#[test]
+fn invalid_reveal() {
+    let (mut env, _library, lot) = auction(MintArgs::default(), SEALED);
+    let alice = env.wallet();
+
+    env.ok(lot.commit(&alice.pubkey(), PRICE, [1; 32], PRICE), &[&alice]);
+    env.warp(START + DURATION);
+
+    expect_error(
+        env.send(lot.reveal(&alice.pubkey(), PRICE, [2; 32]), &[&alice]),
+        ErrorCode::InvalidReveal
+    );
+}
+
+#[test]
+fn stale_sealed_bid() {
+    let (mut env, library, lot) = auction(MintArgs::default(), SEALED);
+    let alice = env.wallet();
+
+    // Alice never reveals, the lot goes unsold and comes back for a second round
+    env.ok(lot.commit(&alice.pubkey(), PRICE, [1; 32], PRICE), &[&alice]);
+    env.warp(START + DURATION + 601);
+    env.ok(lot.relist(env.now() + DURATION, None), &[&library.authority]);
+    env.warp(env.now() + DURATION);
+
+    expect_error(
+        env.send(lot.reveal(&alice.pubkey(), PRICE, [1; 32]), &[&alice]),
+        ErrorCode::StaleSealedBid
+    );
+
+    // A cancelled auction leaves its commitments behind just the same
+    let (mut env, library, lot) = auction(MintArgs::default(), SEALED);
+    let alice = env.wallet();
+
+    env.ok(lot.commit(&alice.pubkey(), PRICE, [1; 32], PRICE), &[&alice]);
+    env.ok(lot.cancel(&lot.seller), &[&library.authority]);
+    env.ok(lot.start(None, env.now() + DURATION, 0, SEALED, None), &[&library.authority]);
+    env.warp(env.now() + DURATION);
+
+    expect_error(
+        env.send(lot.reveal(&alice.pubkey(), PRICE, [1; 32]), &[&alice]),
+        ErrorCode::StaleSealedBid
+    );
+}
+
+#[test]
+fn seller_cannot_bid() {
+    let (mut env, library, lot) = auction(
+        MintArgs { buy_now_price: Some(2 * PRICE), ..MintArgs::default() },
+        AuctionType::English
+    );
+    let seller = &library.authority;
+
+    // A seller bid would read as no bid at all, since the seller holds the slot until the first one
+    expect_error(env.send(lot.bid(&lot.seller, PRICE), &[seller]), ErrorCode::SellerCannotBid);
+    expect_error(env.send(lot.buy_now(&lot.seller), &[seller]), ErrorCode::SellerCannotBid);
+
+    let (mut env, library, lot) = auction(MintArgs::default(), SEALED);
+
+    expect_error(
+        env.send(lot.commit(&lot.seller, PRICE, [1; 32], PRICE), &[&library.authority]),
+        ErrorCode::SellerCannotBid
+    );
+}
+
+/// The test that triggers each variant, `None` for those the program cannot return.
+/// Adding a variant stops this from compiling until it gets a case.
+fn case(code: ErrorCode) -> Option<&'static str> {
+    match code {
+        ErrorCode::AuctionEnded => Some("auction_ended"),
+        ErrorCode::BidTooLow => Some("bid_too_low"),
+        ErrorCode::BidBelowStartingPrice => Some("bid_below_starting_price"),
+        ErrorCode::InvalidHighestBidder => Some("invalid_highest_bidder"),
+        ErrorCode::AuctionTimeExpired => Some("auction_time_expired"),
+        ErrorCode::AuctionStillActive => Some("auction_still_active"),
+        ErrorCode::TimeSetIsNotValid => Some("time_set_is_not_valid"),
+        ErrorCode::InvalidExtensionWindow => Some("invalid_extension_window"),
+        ErrorCode::InvalidDutchAuction => Some("invalid_dutch_auction"),
+        ErrorCode::ReservePriceNotMet => Some("reserve_price_not_met"),
+        ErrorCode::LibraryAccountNotFound => Some("library_account_not_found"),
+        ErrorCode::ArithmeticOverflow => Some("arithmetic_overflow"),
+        ErrorCode::InsufficientFunds => Some("insufficient_funds"),
+        ErrorCode::UnauthorizedTransfer => Some("unauthorized_transfer"),
+        ErrorCode::InvalidReservePrice => Some("invalid_reserve_price"),
+        ErrorCode::InvalidBuyNowPrice => Some("invalid_buy_now_price"),
+        ErrorCode::BuyNowNotAvailable => Some("buy_now_not_available"),
+        ErrorCode::InvalidAccountOwner => Some("invalid_account_owner"),
+        ErrorCode::UnauthorizedCancel => Some("unauthorized_cancel"),
+        ErrorCode::InvalidRentPayer => Some("invalid_rent_payer"),
+        ErrorCode::InvalidPaymentMint => Some("invalid_payment_mint"),
+        ErrorCode::InvalidPaymentAccount => Some("invalid_payment_account"),
+        ErrorCode::InvalidFeeBasisPoints => Some("invalid_fee_basis_points"),
+        ErrorCode::InvalidProtocolFeeAccount => Some("invalid_protocol_fee_account"),
+        ErrorCode::InvalidCreatorAccount => Some("invalid_creator_account"),
+        ErrorCode::InvalidLegacyAccount => Some("invalid_legacy_account"),
+        ErrorCode::UnauthorizedLibraryAuthority => Some("unauthorized_library_authority"),
+        ErrorCode::TooManyLibraryDelegates => Some("too_many_library_delegates"),
+        ErrorCode::LibraryFieldTooLong => Some("library_field_too_long"),
+        ErrorCode::MetadataFieldTooLong => Some("metadata_field_too_long"),
+        ErrorCode::InvalidCreatorShares => Some("invalid_creator_shares"),
+        ErrorCode::InvalidBatchMint => Some("invalid_batch_mint"),
+        ErrorCode::InvalidPendingRefund => Some("invalid_pending_refund"),
+        ErrorCode::RefundLocked => Some("refund_locked"),
+        ErrorCode::AuctionHasWinner => Some("auction_has_winner"),
+        ErrorCode::AuctionNotStarted => Some("auction_not_started"),
+        ErrorCode::AuctionAlreadyStarted => Some("auction_already_started"),
+        ErrorCode::GateNotSatisfied => Some("gate_not_satisfied"),
+        ErrorCode::SealedBidsOnly => Some("sealed_bids_only"),
+        ErrorCode::NotSealedAuction => Some("not_sealed_auction"),
+        ErrorCode::RevealNotActive => Some("reveal_not_active"),
+        ErrorCode::InvalidReveal => Some("invalid_reveal"),
+        ErrorCode::StaleSealedBid => Some("stale_sealed_bid"),
+        ErrorCode::UnauthorizedMigration => Some("unauthorized_migration"),
+        ErrorCode::SellerCannotBid => Some("seller_cannot_bid"),
+        ErrorCode::InvalidPreviousBidder => Some("invalid_previous_bidder"),
+        // Dutch auctions always have a price, the guard in `bid_nft` never fires
+        ErrorCode::AuctionNotActive => None,
+        // Fees are capped at `MAX_BASIS_POINTS` of the final price, the seller share cannot underflow
+        ErrorCode::FeesExceedFinalPrice => None,
+        ErrorCode::InvalidAccountData |
+        ErrorCode::BidderAccountNotFound |
+        ErrorCode::BidderAccountNotInitialized |
+        ErrorCode::InvalidPrice |
+        ErrorCode::InvalidBidStep |
+        ErrorCode::InvalidLibraryName |
+        ErrorCode::InvalidNftName => None,
+    }
+}
+
+/// Retired variants keep their slot so the error numbers clients see stay stable
+#[test]
+fn retired_error_codes_stay_unused() {
+    let retired = [
+        ErrorCode::InvalidAccountData,
+        ErrorCode::BidderAccountNotFound,
+        ErrorCode::BidderAccountNotInitialized,
+        ErrorCode::InvalidPrice,
+        ErrorCode::InvalidBidStep,
+        ErrorCode::InvalidLibraryName,
+        ErrorCode::InvalidNftName,
+    ];
+    let mut sources = String::new();
+    read_sources(&Path::new(env!("CARGO_MANIFEST_DIR")).join("src"), &mut sources);
+
+    for code in retired {
+        let usage = format!("ErrorCode::{code:?}");
+
+        assert_eq!(case(code), None);
+        assert!(!sources.contains(&usage), "{usage} is returned now, give it a test and a case");
+    }
+}
+
+/// Codes of the variants the deployed program already returned, new variants go after them
+#[test]
+fn deployed_error_codes_keep_their_numbers() {
+    let deployed = [
+        ErrorCode::AuctionEnded,
+        ErrorCode::BidTooLow,
+        ErrorCode::BidBelowStartingPrice,
+        ErrorCode::InvalidHighestBidder,
+        ErrorCode::AuctionNotActive,
+        ErrorCode::AuctionTimeExpired,
+        ErrorCode::AuctionStillActive,
+        ErrorCode::TimeSetIsNotValid,
+        ErrorCode::LibraryAccountNotFound,
+        ErrorCode::InvalidAccountData,
+        ErrorCode::BidderAccountNotFound,
+        ErrorCode::BidderAccountNotInitialized,
+        ErrorCode::ArithmeticOverflow,
+        ErrorCode::InsufficientFunds,
+        ErrorCode::UnauthorizedTransfer,
+        ErrorCode::InvalidPrice,
+        ErrorCode::InvalidBidStep,
+        ErrorCode::InvalidLibraryName,
+        ErrorCode::InvalidNftName,
+        ErrorCode::InvalidPreviousBidder,
+        ErrorCode::InvalidAccountOwner,
+    ];
+
+    for (offset, code) in deployed.into_iter().enumerate() {
+        assert_eq!(u32::from(code), 6_000 + (offset as u32), "{code:?} moved");
+    }
+}
+
+fn read_sources(dir: &Path, sources: &mut String) {
+    for entry in fs::read_dir(dir).unwrap() {
+        let path = entry.unwrap().path();
+
+        if path.is_dir() {
+            read_sources(&path, sources);
+        } else if path.extension().is_some_and(|extension| extension == "rs") {
+            sources.push_str(&fs::read_to_string(&path).unwrap());
+        }
+    }
+}
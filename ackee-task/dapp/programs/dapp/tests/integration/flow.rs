@@ -0,0 +1,439 @@
+use anchor_lang::{ prelude::Pubkey, Space };
+use dapp::{
+    constants::MAX_BATCH_MINT_ITEMS,
+    AuctionState,
+    AuctionType,
+    EscrowBidder,
+    NftInfo,
+    PendingRefund,
+};
+use solana_signer::Signer;
+
+use crate::harness::*;
+
+fn protocol_fee(price: u64) -> u64 {
+    price * (PROTOCOL_FEE_BASIS_POINTS as u64) / 10_000
+}
+
+#[test]
+fn english_auction_sells_to_the_highest_bidder() {
+    let mut env = TestEnv::new();
+    let library = env.library("Books");
+    let lot = env.auction(&library, "Dune", MintArgs::default(), AuctionType::English);
+    let (alice, bob) = (env.wallet(), env.wallet());
+    let final_price = PRICE + 2 * STEP;
+
+    env.ok(lot.bid(&alice.pubkey(), PRICE), &[&alice]);
+    env.ok(lot.bid(&bob.pubkey(), PRICE + STEP), &[&bob]);
+    env.ok(lot.bid(&alice.pubkey(), final_price), &[&alice]);
+
+    // Raising a bid only tops up what was already locked
+    let alice_refund = env.account::<PendingRefund>(&lot.pending_refund(&alice.pubkey()));
+    assert_eq!(alice_refund.amount, final_price);
+
+    env.warp(START + DURATION + 1);
+
+    let seller_before = env.balance(&lot.seller);
+    let fees_before = env.balance(&library.protocol_fee_account);
+    let closed_rent = env.balance(&lot.nft_info()) + env.balance(&lot.auction_state());
+
+    env.ok(lot.transfer(&alice.pubkey()), &[&library.authority]);
+
+    assert_eq!(env.token_amount(&lot.nft_account(&alice.pubkey())), 1);
+    assert_eq!(env.balance(&library.protocol_fee_account) - fees_before, protocol_fee(final_price));
+    // The seller is also the only creator, so the royalty comes back to it
+    assert_eq!(
+        env.balance(&lot.seller) - seller_before,
+        final_price - protocol_fee(final_price) + closed_rent
+    );
+    assert!(!env.exists(&lot.pending_refund(&alice.pubkey())));
+    assert!(!env.exists(&lot.nft_info()));
+    assert!(!env.exists(&lot.auction_state()));
+
+    // Bob's outbid funds keep the escrow open until he pulls them
+    assert!(env.exists(&lot.escrow()));
+
+    let bob_before = env.balance(&bob.pubkey());
+    let record_rent = env.balance(&lot.pending_refund(&bob.pubkey()));
+
+    env.ok(lot.withdraw_refund(&bob.pubkey()), &[&bob]);
+
+    assert_eq!(env.balance(&bob.pubkey()) - bob_before, PRICE + STEP + record_rent);
+    assert!(!env.exists(&lot.escrow()));
+}
+
+#[test]
+fn settle_auction_can_be_cranked_by_anyone() {
+    let mut env = TestEnv::new();
+    let library = env.library("Books");
+    let lot = env.auction(&library, "Dune", MintArgs::default(), AuctionType::English);
+    let (alice, cranker) = (env.wallet(), env.wallet());
+
+    env.ok(lot.bid(&alice.pubkey(), PRICE), &[&alice]);
+    env.warp(START + DURATION + 1);
+
+    let fees_before = env.balance(&library.protocol_fee_account);
+
+    env.ok(lot.settle(&cranker.pubkey(), &alice.pubkey()), &[&cranker]);
+
+    assert_eq!(env.token_amount(&lot.nft_account(&alice.pubkey())), 1);
+    assert_eq!(env.balance(&library.protocol_fee_account) - fees_before, protocol_fee(PRICE));
+    assert!(!env.exists(&lot.escrow()));
+}
+
+#[test]
+fn fee_below_rent_exemption_goes_to_the_seller() {
+    let mut env = TestEnv::new();
+    // Nothing funds the protocol wallet, a 1% fee on a small sale cannot make it rent exempt
+    let library = Library {
+        name: "Books".to_string(),
+        authority: env.wallet(),
+        protocol_fee_account: Pubkey::new_unique(),
+    };
+    let price = PRICE / 100;
+    let args = MintArgs { price, ..MintArgs::default() };
+
+    env.ok(
+        library.init(SELLER_FEE_BASIS_POINTS, PROTOCOL_FEE_BASIS_POINTS, Some(library.protocol_fee_account)),
+        &[&library.authority]
+    );
+
+    let lot = env.auction(&library, "Dune", args, AuctionType::English);
+    let alice = env.wallet();
+
+    assert!(protocol_fee(price) < env.rent(0));
+
+    env.ok(lot.bid(&alice.pubkey(), price), &[&alice]);
+    env.warp(START + DURATION + 1);
+
+    let seller_before = env.balance(&lot.seller);
+    // Nobody was outbid, so the escrow closes as well
+    let escrow_rent = env.balance(&lot.escrow()) - price;
+    let closed_rent = env.balance(&lot.nft_info()) + env.balance(&lot.auction_state()) + escrow_rent;
+
+    env.ok(lot.transfer(&alice.pubkey()), &[&library.authority]);
+
+    assert_eq!(env.token_amount(&lot.nft_account(&alice.pubkey())), 1);
+    assert!(!env.exists(&library.protocol_fee_account));
+    assert_eq!(env.balance(&lot.seller) - seller_before, price + closed_rent);
+}
+
+fn batch_item(name: &str) -> dapp::MintNftItem {
+    dapp::MintNftItem {
+        name: name.to_string(),
+        price: PRICE,
+        bid_step: STEP,
+        reserve_price: None,
+        buy_now_price: None,
+        uri: "https://example.com/nft.json".to_string(),
+        symbol: None,
+        creators: None,
+    }
+}
+
+#[test]
+fn batch_mint_takes_over_prefunded_accounts() {
+    let mut env = TestEnv::new();
+    let library = env.library("Books");
+    let items = ["Dune", "Emma"].map(batch_item);
+    let dune = library.lot("Dune");
+
+    // Anyone can send lamports to a PDA before it is created
+    env.svm.airdrop(&dune.nft_info(), 1).unwrap();
+    env.svm.airdrop(&dune.escrow(), 2 * PRICE).unwrap();
+
+    env.ok(library.batch_mint(items.to_vec()), &[&library.authority]);
+
+    for name in ["Dune", "Emma"] {
+        let lot = library.lot(name);
+        let nft_info = env.account::<NftInfo>(&lot.nft_info());
+
+        assert_eq!(nft_info.name, name);
+        assert_eq!(env.token_amount(&lot.escrow_nft_account()), 1);
+    }
+}
+
+#[test]
+fn batch_mint_fits_the_cap_in_one_transaction() {
+    let mut env = TestEnv::new();
+    let library = env.library("Books");
+    let names: Vec<String> = (0..MAX_BATCH_MINT_ITEMS).map(|i| format!("Lot {i}")).collect();
+    let items = names.iter().map(|name| batch_item(name)).collect();
+
+    env.ok(library.batch_mint(items), &[&library.authority]);
+
+    for name in &names {
+        assert_eq!(env.token_amount(&library.lot(name).escrow_nft_account()), 1);
+    }
+}
+
+#[test]
+fn late_bid_extends_the_auction() {
+    let mut env = TestEnv::new();
+    let library = env.library("Books");
+    let lot = library.lot("Dune");
+    let alice = env.wallet();
+    let end_time = START + DURATION;
+
+    env.ok(library.mint("Dune", MintArgs::default()), &[&library.authority]);
+    env.ok(lot.start(None, end_time, 300, AuctionType::English, None), &[&library.authority]);
+
+    env.warp(end_time - 10);
+    env.ok(lot.bid(&alice.pubkey(), PRICE), &[&alice]);
+
+    let auction_state = env.account::<AuctionState>(&lot.auction_state());
+    assert_eq!(auction_state.auction_end_time, end_time + 300);
+}
+
+#[test]
+fn unsold_lot_is_relisted_and_withdrawn() {
+    let mut env = TestEnv::new();
+    let library = env.library("Books");
+    let lot = env.auction(&library, "Dune", MintArgs::default(), AuctionType::English);
+    let seller = library.authority.pubkey();
+
+    env.warp(START + DURATION + 1);
+    env.ok(lot.settle(&seller, &seller), &[&library.authority]);
+
+    // Nobody bid, the NFT waits in escrow for the seller to decide
+    assert_eq!(env.token_amount(&lot.escrow_nft_account()), 1);
+    assert!(env.exists(&lot.auction_state()));
+
+    env.ok(lot.relist(env.now() + DURATION, Some(PRICE / 2)), &[&library.authority]);
+    env.warp(env.now() + DURATION + 1);
+    env.ok(lot.withdraw_unsold(), &[&library.authority]);
+
+    assert_eq!(env.token_amount(&lot.nft_account(&seller)), 1);
+    assert!(!env.exists(&lot.nft_info()));
+    assert!(!env.exists(&lot.auction_state()));
+    assert!(!env.exists(&lot.escrow()));
+}
+
+#[test]
+fn bid_below_the_reserve_is_refunded() {
+    let mut env = TestEnv::new();
+    let library = env.library("Books");
+    let args = MintArgs { reserve_price: Some(2 * PRICE), ..MintArgs::default() };
+    let lot = env.auction(&library, "Dune", args, AuctionType::English);
+    let alice = env.wallet();
+
+    env.ok(lot.bid(&alice.pubkey(), PRICE), &[&alice]);
+    env.warp(START + DURATION + 1);
+    env.ok(lot.settle(&alice.pubkey(), &alice.pubkey()), &[&alice]);
+
+    assert_eq!(env.token_amount(&lot.escrow_nft_account()), 1);
+
+    let alice_before = env.balance(&alice.pubkey());
+    let record_rent = env.balance(&lot.pending_refund(&alice.pubkey()));
+
+    env.ok(lot.withdraw_refund(&alice.pubkey()), &[&alice]);
+
+    assert_eq!(env.balance(&alice.pubkey()) - alice_before, PRICE + record_rent);
+}
+
+#[test]
+fn cancelled_lot_can_be_auctioned_again() {
+    let mut env = TestEnv::new();
+    let library = env.library("Books");
+    let lot = env.auction(&library, "Dune", MintArgs::default(), AuctionType::English);
+    let (alice, bob) = (env.wallet(), env.wallet());
+
+    env.ok(lot.bid(&alice.pubkey(), PRICE + STEP), &[&alice]);
+    env.ok(lot.cancel(&lot.seller), &[&library.authority]);
+
+    // Only the auction is gone, the lot and its NFT stay in place for the next one
+    assert!(!env.exists(&lot.auction_state()));
+    assert_eq!(env.token_amount(&lot.escrow_nft_account()), 1);
+
+    let nft_info = env.account::<NftInfo>(&lot.nft_info());
+    assert_eq!(nft_info.current_bidder, lot.seller);
+    assert_eq!(nft_info.current_price, PRICE);
+
+    let alice_before = env.balance(&alice.pubkey());
+    let record_rent = env.balance(&lot.pending_refund(&alice.pubkey()));
+
+    env.ok(lot.withdraw_refund(&alice.pubkey()), &[&alice]);
+
+    assert_eq!(env.balance(&alice.pubkey()) - alice_before, PRICE + STEP + record_rent);
+
+    env.ok(lot.start(None, START + 2 * DURATION, 0, AuctionType::English, None), &[&library.authority]);
+    env.ok(lot.bid(&bob.pubkey(), PRICE), &[&bob]);
+
+    env.warp(START + 2 * DURATION + 1);
+    env.ok(lot.settle(&bob.pubkey(), &bob.pubkey()), &[&bob]);
+
+    assert_eq!(env.token_amount(&lot.nft_account(&bob.pubkey())), 1);
+    assert!(!env.exists(&lot.escrow()));
+}
+
+#[test]
+fn buy_now_settles_immediately() {
+    let mut env = TestEnv::new();
+    let library = env.library("Books");
+    let args = MintArgs { buy_now_price: Some(3 * PRICE), ..MintArgs::default() };
+    let lot = env.auction(&library, "Dune", args, AuctionType::English);
+    let (alice, bob) = (env.wallet(), env.wallet());
+
+    env.ok(lot.bid(&alice.pubkey(), PRICE), &[&alice]);
+    env.ok(lot.buy_now(&bob.pubkey()), &[&bob]);
+
+    assert_eq!(env.token_amount(&lot.nft_account(&bob.pubkey())), 1);
+    assert!(!env.exists(&lot.auction_state()));
+
+    env.ok(lot.withdraw_refund(&alice.pubkey()), &[&alice]);
+
+    assert!(!env.exists(&lot.escrow()));
+}
+
+#[test]
+fn dutch_bid_settles_at_the_current_price() {
+    let mut env = TestEnv::new();
+    let library = env.library("Books");
+    let dutch = AuctionType::Dutch {
+        start_price: 3 * PRICE,
+        floor_price: PRICE,
+        price_drop: PRICE,
+        drop_interval: 600,
+    };
+    let lot = env.auction(&library, "Dune", MintArgs::default(), dutch);
+    let alice = env.wallet();
+
+    env.warp(START + 600);
+
+    let alice_before = env.balance(&alice.pubkey());
+
+    // Bidding above the current price only locks the current price
+    env.ok(lot.bid(&alice.pubkey(), 3 * PRICE), &[&alice]);
+
+    let nft_account = lot.nft_account(&alice.pubkey());
+    assert_eq!(env.token_amount(&nft_account), 1);
+    assert_eq!(alice_before - env.balance(&alice.pubkey()), 2 * PRICE + env.balance(&nft_account));
+    assert!(!env.exists(&lot.pending_refund(&alice.pubkey())));
+}
+
+#[test]
+fn relisted_dutch_lot_drops_from_the_new_start_price() {
+    let mut env = TestEnv::new();
+    let library = env.library("Books");
+    let dutch = AuctionType::Dutch {
+        start_price: 3 * PRICE,
+        floor_price: PRICE,
+        price_drop: PRICE,
+        drop_interval: 600,
+    };
+    let lot = env.auction(&library, "Dune", MintArgs::default(), dutch);
+    let seller = library.authority.pubkey();
+    let alice = env.wallet();
+
+    // Nobody bid, by now the price sits at the floor
+    env.warp(START + DURATION + 1);
+    env.ok(lot.settle(&seller, &seller), &[&library.authority]);
+    env.ok(lot.relist(env.now() + DURATION, Some(4 * PRICE)), &[&library.authority]);
+
+    let alice_before = env.balance(&alice.pubkey());
+
+    env.ok(lot.bid(&alice.pubkey(), 4 * PRICE), &[&alice]);
+
+    let nft_account = lot.nft_account(&alice.pubkey());
+    assert_eq!(env.token_amount(&nft_account), 1);
+    assert_eq!(alice_before - env.balance(&alice.pubkey()), 4 * PRICE + env.balance(&nft_account));
+}
+
+#[test]
+fn sealed_second_price_auction_charges_the_runner_up_bid() {
+    let mut env = TestEnv::new();
+    let library = env.library("Books");
+    let sealed = AuctionType::Sealed { reveal_window: 600, second_price: true };
+    let lot = env.auction(&library, "Dune", MintArgs::default(), sealed);
+    let (alice, bob, cranker) = (env.wallet(), env.wallet(), env.wallet());
+
+    // Alice hides her bid behind a larger deposit
+    env.ok(lot.commit(&alice.pubkey(), 3 * PRICE, [1; 32], 4 * PRICE), &[&alice]);
+    env.ok(lot.commit(&bob.pubkey(), 2 * PRICE, [2; 32], 2 * PRICE), &[&bob]);
+
+    env.warp(START + DURATION);
+    env.ok(lot.reveal(&alice.pubkey(), 3 * PRICE, [1; 32]), &[&alice]);
+    env.ok(lot.reveal(&bob.pubkey(), 2 * PRICE, [2; 32]), &[&bob]);
+
+    env.warp(START + DURATION + 601);
+
+    let fees_before = env.balance(&library.protocol_fee_account);
+
+    env.ok(lot.settle(&cranker.pubkey(), &alice.pubkey()), &[&cranker]);
+
+    assert_eq!(env.token_amount(&lot.nft_account(&alice.pubkey())), 1);
+    assert_eq!(env.balance(&library.protocol_fee_account) - fees_before, protocol_fee(2 * PRICE));
+
+    let alice_refund = env.account::<PendingRefund>(&lot.pending_refund(&alice.pubkey()));
+    assert_eq!(alice_refund.amount, 2 * PRICE);
+
+    env.ok(lot.withdraw_refund(&alice.pubkey()), &[&alice]);
+    env.ok(lot.withdraw_refund(&bob.pubkey()), &[&bob]);
+
+    assert!(!env.exists(&lot.escrow()));
+}
+
+#[test]
+fn legacy_lot_sells_after_migration() {
+    let mut env = TestEnv::new();
+    let (library, lot) = env.legacy_library("Books", "Dune");
+    let (admin, alice, cranker) = (env.wallet(), env.wallet(), env.wallet());
+
+    env.set_upgrade_authority(&admin.pubkey());
+    env.ok(library.migrate(&admin.pubkey()), &[&admin]);
+    env.ok(lot.migrate(), &[&library.authority]);
+
+    let nft_info = env.account::<NftInfo>(&lot.nft_info());
+    assert_eq!(nft_info.nft_mint, lot.nft_mint());
+    assert_eq!(nft_info.current_bidder, lot.seller);
+    assert!(!env.exists(&pda(&[b"nft_info", b"Dune"])));
+
+    env.ok(lot.start(None, START + DURATION, 0, AuctionType::English, None), &[&library.authority]);
+    env.ok(lot.bid(&alice.pubkey(), PRICE + STEP), &[&alice]);
+
+    env.warp(START + DURATION + 1);
+
+    let fees_before = env.balance(&library.protocol_fee_account);
+
+    env.ok(lot.settle(&cranker.pubkey(), &alice.pubkey()), &[&cranker]);
+
+    assert_eq!(env.token_amount(&lot.nft_account(&alice.pubkey())), 1);
+    assert_eq!(env.balance(&library.protocol_fee_account) - fees_before, protocol_fee(PRICE + STEP));
+    assert!(!env.exists(&lot.nft_info()));
+}
+
+#[test]
+fn legacy_auction_continues_after_migration() {
+    let mut env = TestEnv::new();
+    let (library, lot) = env.legacy_library("Books", "Dune");
+    let (admin, alice, bob) = (env.wallet(), env.wallet(), env.wallet());
+
+    env.set_upgrade_authority(&admin.pubkey());
+    env.ok(library.migrate(&admin.pubkey()), &[&admin]);
+    env.legacy_auction(&lot, START + DURATION, Some((&alice.pubkey(), PRICE + STEP)));
+
+    env.ok(lot.migrate_with(true, Some(&alice.pubkey())), &[&library.authority]);
+
+    assert!(!env.exists(&pda(&[b"auction", b"Dune"])));
+    assert_eq!(env.account::<AuctionState>(&lot.auction_state()).auction_end_time, START + DURATION);
+    assert_eq!(env.account::<PendingRefund>(&lot.pending_refund(&alice.pubkey())).amount, PRICE + STEP);
+
+    // The seller made up the starting price the legacy bid paid out, the whole bid is in escrow
+    let escrow_rent = env.rent(8 + EscrowBidder::INIT_SPACE);
+    assert_eq!(env.balance(&lot.escrow()), escrow_rent + PRICE + STEP);
+
+    env.ok(lot.bid(&bob.pubkey(), PRICE + 2 * STEP), &[&bob]);
+
+    let alice_before = env.balance(&alice.pubkey());
+    let record_rent = env.balance(&lot.pending_refund(&alice.pubkey()));
+
+    env.ok(lot.withdraw_refund(&alice.pubkey()), &[&alice]);
+
+    assert_eq!(env.balance(&alice.pubkey()) - alice_before, PRICE + STEP + record_rent);
+
+    env.warp(START + DURATION + 1);
+    env.ok(lot.settle(&bob.pubkey(), &bob.pubkey()), &[&bob]);
+
+    assert_eq!(env.token_amount(&lot.nft_account(&bob.pubkey())), 1);
+    assert!(!env.exists(&lot.escrow()));
+}
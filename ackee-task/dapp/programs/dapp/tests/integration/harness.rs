@@ -0,0 +1,1101 @@
+use anchor_lang::{
+    prelude::{ AccountMeta, Clock, Pubkey },
+    solana_program::{
+        instruction::{ Instruction, InstructionError },
+        program_option::COption,
+        program_pack::Pack,
+        sysvar,
+    },
+    system_program,
+    AccountDeserialize,
+    AnchorDeserialize,
+    AnchorSerialize,
+    Discriminator,
+    Id,
+    InstructionData,
+    ToAccountMetas,
+};
+use anchor_spl::{
+    associated_token::{ self, get_associated_token_address },
+    metadata::Metadata,
+    token::{ self, spl_token },
+};
+use dapp::{
+    enums::ErrorCode,
+    AuctionGate,
+    AuctionState,
+    AuctionType,
+    LegacyAuctionState,
+    LegacyNftInfo,
+    NftCreator,
+    NftInfo,
+    SealedBid,
+    constants::*,
+};
+use litesvm::{ types::TransactionResult, LiteSVM };
+use solana_account::Account;
+use solana_compute_budget_interface::ComputeBudgetInstruction;
+use solana_keypair::Keypair;
+use solana_loader_v3_interface::state::UpgradeableLoaderState;
+use solana_sdk_ids::bpf_loader_upgradeable;
+use solana_signer::Signer;
+use solana_transaction::Transaction;
+use solana_transaction_error::TransactionError;
+
+use crate::native;
+
+pub const START: i64 = 1_700_000_000;
+pub const DURATION: i64 = 3_600;
+pub const PRICE: u64 = 1_000_000_000;
+pub const STEP: u64 = 100_000_000;
+pub const SELLER_FEE_BASIS_POINTS: u16 = 500;
+pub const PROTOCOL_FEE_BASIS_POINTS: u16 = 100;
+
+// Largest serialized transaction the cluster accepts
+const PACKET_DATA_SIZE: usize = 1232;
+
+const DAPP_SO: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/../../target/deploy/dapp.so");
+
+const TOKEN_METADATA_SO: &str = concat!(
+    env!("CARGO_MANIFEST_DIR"),
+    "/tests/fixtures/mpl_token_metadata.so"
+);
+
+/// A LiteSVM bank with the dapp and Metaplex token metadata programs loaded, the clock starts at `START`
+///
+/// The dapp program is the `anchor build` output, or `DAPP_SO` if set. `DAPP_NATIVE` runs it
+/// natively instead, without compute metering or SBF memory checks.
+pub struct TestEnv {
+    pub svm: LiteSVM,
+    payer: Keypair,
+}
+
+impl TestEnv {
+    pub fn new() -> Self {
+        let mut svm = LiteSVM::new();
+
+        if std::env::var_os("DAPP_NATIVE").is_some() {
+            native::add_dapp(&mut svm);
+        } else {
+            let path = std::env::var("DAPP_SO").unwrap_or_else(|_| DAPP_SO.to_string());
+
+            svm.add_program_from_file(dapp::ID, &path).unwrap_or_else(|_| {
+                panic!("{path} is missing, run `anchor build` first or set DAPP_NATIVE=1")
+            });
+        }
+        svm.add_program_from_file(Metadata::id(), TOKEN_METADATA_SO).unwrap();
+
+        let payer = Keypair::new();
+        svm.airdrop(&payer.pubkey(), 1_000 * PRICE).unwrap();
+
+        let mut env = Self { svm, payer };
+        env.warp(START);
+        env
+    }
+
+    pub fn now(&self) -> i64 {
+        self.svm.get_sysvar::<Clock>().unix_timestamp
+    }
+
+    pub fn warp(&mut self, unix_timestamp: i64) {
+        let mut clock = self.svm.get_sysvar::<Clock>();
+        clock.slot += 1;
+        clock.unix_timestamp = unix_timestamp;
+        self.svm.set_sysvar(&clock);
+    }
+
+    pub fn wallet(&mut self) -> Keypair {
+        let wallet = Keypair::new();
+        self.svm.airdrop(&wallet.pubkey(), 100 * PRICE).unwrap();
+        wallet
+    }
+
+    /// Sends `instruction` with a separate fee payer, so wallet balances only move by what the program does
+    #[allow(clippy::result_large_err)]
+    pub fn send(&mut self, instruction: Instruction, signers: &[&Keypair]) -> TransactionResult {
+        // Identical transactions would otherwise be rejected as already processed
+        self.svm.expire_blockhash();
+
+        let mut all_signers = vec![&self.payer];
+        all_signers.extend_from_slice(signers);
+
+        let transaction = Transaction::new_signed_with_payer(
+            &[ComputeBudgetInstruction::set_compute_unit_limit(1_400_000), instruction],
+            Some(&self.payer.pubkey()),
+            &all_signers,
+            self.svm.latest_blockhash()
+        );
+
+        // LiteSVM takes transactions of any size, a validator would drop this one
+        let size = bincode::serialize(&transaction).unwrap().len();
+        assert!(size <= PACKET_DATA_SIZE, "transaction is {size} bytes, over {PACKET_DATA_SIZE}");
+
+        self.svm.send_transaction(transaction)
+    }
+
+    pub fn ok(&mut self, instruction: Instruction, signers: &[&Keypair]) {
+        if let Err(failed) = self.send(instruction, signers) {
+            panic!("{:?}\n{}", failed.err, failed.meta.pretty_logs());
+        }
+    }
+
+    pub fn exists(&self, address: &Pubkey) -> bool {
+        self.svm.get_account(address).is_some_and(|account| account.lamports > 0)
+    }
+
+    pub fn balance(&self, address: &Pubkey) -> u64 {
+        self.svm.get_balance(address).unwrap_or_default()
+    }
+
+    pub fn rent(&self, data_len: usize) -> u64 {
+        self.svm.minimum_balance_for_rent_exemption(data_len)
+    }
+
+    pub fn account<T: AccountDeserialize>(&self, address: &Pubkey) -> T {
+        let account = self.svm.get_account(address).expect("account does not exist");
+
+        T::try_deserialize(&mut account.data.as_slice()).unwrap()
+    }
+
+    pub fn token_amount(&self, address: &Pubkey) -> u64 {
+        let account = self.svm.get_account(address).expect("token account does not exist");
+
+        spl_token::state::Account::unpack(&account.data).unwrap().amount
+    }
+
+    pub fn set_token_amount(&mut self, address: &Pubkey, amount: u64) {
+        let mut account = self.svm.get_account(address).expect("token account does not exist");
+        let mut token_account = spl_token::state::Account::unpack(&account.data).unwrap();
+
+        token_account.amount = amount;
+        spl_token::state::Account::pack(token_account, &mut account.data).unwrap();
+
+        self.svm.set_account(*address, account).unwrap();
+    }
+
+    /// Writes an account directly, for states the program cannot reach on its own
+    pub fn set_data(&mut self, address: Pubkey, owner: Pubkey, data: Vec<u8>) {
+        let account = Account {
+            lamports: self.rent(data.len()),
+            data,
+            owner,
+            executable: false,
+            rent_epoch: 0,
+        };
+
+        self.svm.set_account(address, account).unwrap();
+    }
+
+    /// Makes `authority` the upgrade authority of the dapp program, as `migrate_library` expects
+    pub fn set_upgrade_authority(&mut self, authority: &Pubkey) {
+        let program_data = Pubkey::find_program_address(
+            &[dapp::ID.as_ref()],
+            &bpf_loader_upgradeable::ID
+        ).0;
+        let state = UpgradeableLoaderState::ProgramData {
+            slot: 0,
+            upgrade_authority_address: Some(*authority),
+        };
+
+        self.set_data(program_data, bpf_loader_upgradeable::ID, bincode::serialize(&state).unwrap());
+    }
+
+    /// `library` with `lot_name` minted the way the program did before library scoping
+    ///
+    /// There is no `Library` or `LibraryConfig` yet, the `NftInfo` lives at `[b"nft_info", name]`
+    /// in its old layout and the mint at `[name]`, with metadata and escrow token account to match.
+    pub fn legacy_library(&mut self, name: &str, lot_name: &str) -> (Library, Lot) {
+        let library = self.library(name);
+        let lot = library.lot(lot_name);
+        let legacy = library.legacy_lot(lot_name);
+
+        self.ok(library.mint(lot_name, MintArgs::default()), &[&library.authority]);
+
+        let nft_info = self.account::<NftInfo>(&lot.nft_info());
+        let (legacy_nft_info, bump) = Pubkey::find_program_address(
+            &[BUID_NFT_SEEDS, lot_name.as_bytes()],
+            &dapp::ID
+        );
+        let mut data = NftInfo::DISCRIMINATOR.to_vec();
+
+        (LegacyNftInfo {
+            current_price: nft_info.current_price,
+            starting_price: nft_info.starting_price,
+            current_bidder: nft_info.current_bidder,
+            bid_step: nft_info.bid_step,
+            bump,
+            library_name: nft_info.library_name,
+            name: nft_info.name,
+        })
+            .serialize(&mut data)
+            .unwrap();
+        self.set_data(legacy_nft_info, dapp::ID, data);
+
+        let mut mint = self.svm.get_account(&lot.nft_mint()).unwrap();
+        let mut mint_state = spl_token::state::Mint::unpack(&mint.data).unwrap();
+
+        mint_state.mint_authority = COption::Some(master_edition_pda(&legacy.nft_mint()));
+        spl_token::state::Mint::pack(mint_state, &mut mint.data).unwrap();
+        self.svm.set_account(legacy.nft_mint(), mint).unwrap();
+
+        // Metaplex metadata starts with the key byte and the update authority, then the mint
+        let mut metadata = self.svm.get_account(&metadata_pda(&lot.nft_mint())).unwrap();
+
+        metadata.data[33..65].copy_from_slice(legacy.nft_mint().as_ref());
+        self.svm.set_account(metadata_pda(&legacy.nft_mint()), metadata).unwrap();
+
+        let master_edition = self.svm.get_account(&master_edition_pda(&lot.nft_mint())).unwrap();
+
+        self.svm.set_account(master_edition_pda(&legacy.nft_mint()), master_edition).unwrap();
+
+        let mut escrow_nft = self.svm.get_account(&lot.escrow_nft_account()).unwrap();
+        let mut token_account = spl_token::state::Account::unpack(&escrow_nft.data).unwrap();
+
+        token_account.mint = legacy.nft_mint();
+        spl_token::state::Account::pack(token_account, &mut escrow_nft.data).unwrap();
+        self.svm.set_account(legacy.escrow_nft_account(), escrow_nft).unwrap();
+
+        for address in [
+            lot.nft_info(),
+            lot.nft_mint(),
+            metadata_pda(&lot.nft_mint()),
+            master_edition_pda(&lot.nft_mint()),
+            lot.escrow_nft_account(),
+            library.address(),
+            library.config_address(),
+        ] {
+            self.svm.set_account(address, Account::default()).unwrap();
+        }
+
+        (library, legacy)
+    }
+
+    /// Opens a legacy auction on a `legacy_library` lot, led by `bid` when there is one
+    ///
+    /// Legacy bids refunded the previous leader out of the escrow, the first one paid the starting
+    /// price to the minter, so the escrow only holds the bid steps on top of its rent.
+    pub fn legacy_auction(&mut self, lot: &Lot, end_time: i64, bid: Option<(&Pubkey, u64)>) {
+        let mut data = AuctionState::DISCRIMINATOR.to_vec();
+
+        (LegacyAuctionState {
+            nft_mint: Pubkey::default(),
+            seller: lot.seller,
+            auction_end_time: end_time,
+            bump: 0,
+        })
+            .serialize(&mut data)
+            .unwrap();
+        self.set_data(pda(&[AUCTION_STATE_SEEDS, lot.name.as_bytes()]), dapp::ID, data);
+
+        let Some((bidder, amount)) = bid else {
+            return;
+        };
+
+        let legacy_nft_info = pda(&[BUID_NFT_SEEDS, lot.name.as_bytes()]);
+        let account = self.svm.get_account(&legacy_nft_info).unwrap();
+        let mut nft_info = LegacyNftInfo::deserialize(&mut &account.data[8..]).unwrap();
+        let bid_steps = amount - nft_info.starting_price;
+
+        nft_info.current_bidder = *bidder;
+        nft_info.current_price = amount;
+
+        let mut data = NftInfo::DISCRIMINATOR.to_vec();
+        nft_info.serialize(&mut data).unwrap();
+        self.set_data(legacy_nft_info, dapp::ID, data);
+
+        let mut escrow = self.svm.get_account(&lot.escrow()).unwrap();
+
+        // Every legacy bid also made its bidder the escrow payer
+        escrow.data[8..40].copy_from_slice(bidder.as_ref());
+        escrow.lamports = self.rent(escrow.data.len()) + bid_steps;
+        self.svm.set_account(lot.escrow(), escrow).unwrap();
+    }
+
+    /// A library with a 5% royalty and a 1% protocol fee, its authority mints and sells every lot
+    pub fn library(&mut self, name: &str) -> Library {
+        let authority = self.wallet();
+        let protocol_fee_account = self.wallet().pubkey();
+        let library = Library {
+            name: name.to_string(),
+            authority,
+            protocol_fee_account,
+        };
+
+        self.ok(
+            library.init(SELLER_FEE_BASIS_POINTS, PROTOCOL_FEE_BASIS_POINTS, Some(protocol_fee_account)),
+            &[&library.authority]
+        );
+
+        library
+    }
+
+    /// Mints `name` into the library and opens an auction on it that ends `DURATION` from now
+    pub fn auction(
+        &mut self,
+        library: &Library,
+        name: &str,
+        args: MintArgs,
+        auction_type: AuctionType
+    ) -> Lot {
+        let lot = library.lot(name);
+
+        self.ok(library.mint(name, args), &[&library.authority]);
+        self.ok(
+            lot.start(None, self.now() + DURATION, 0, auction_type, None),
+            &[&library.authority]
+        );
+
+        lot
+    }
+}
+
+pub fn expect_error(result: TransactionResult, code: ErrorCode) {
+    let expected = u32::from(code);
+
+    match result {
+        Err(failed) =>
+            match failed.err {
+                TransactionError::InstructionError(_, InstructionError::Custom(found)) if
+                    found == expected
+                => {}
+                err => panic!("expected {code:?}, got {err:?}\n{}", failed.meta.pretty_logs()),
+            }
+        Ok(_) => panic!("expected {code:?}, the transaction succeeded"),
+    }
+}
+
+pub fn pda(seeds: &[&[u8]]) -> Pubkey {
+    Pubkey::find_program_address(seeds, &dapp::ID).0
+}
+
+pub fn metadata_pda(mint: &Pubkey) -> Pubkey {
+    let metadata_program = Metadata::id();
+    let seeds: &[&[u8]] = &[LIBRARY_METADATA_SEEDS, metadata_program.as_ref(), mint.as_ref()];
+
+    Pubkey::find_program_address(seeds, &metadata_program).0
+}
+
+pub fn master_edition_pda(mint: &Pubkey) -> Pubkey {
+    let metadata_program = Metadata::id();
+    let seeds: &[&[u8]] = &[
+        LIBRARY_METADATA_SEEDS,
+        metadata_program.as_ref(),
+        mint.as_ref(),
+        LIBRARY_MASTER_EDITION_SEEDS,
+    ];
+
+    Pubkey::find_program_address(seeds, &metadata_program).0
+}
+
+pub fn instruction(
+    accounts: impl ToAccountMetas,
+    data: impl InstructionData,
+    remaining_accounts: &[AccountMeta]
+) -> Instruction {
+    let mut metas = accounts.to_account_metas(None);
+    metas.extend_from_slice(remaining_accounts);
+
+    Instruction {
+        program_id: dapp::ID,
+        accounts: metas,
+        data: data.data(),
+    }
+}
+
+#[derive(Clone)]
+pub struct MintArgs {
+    pub price: u64,
+    pub bid_step: u64,
+    pub reserve_price: Option<u64>,
+    pub buy_now_price: Option<u64>,
+    pub uri: String,
+    pub creators: Option<Vec<NftCreator>>,
+}
+
+impl Default for MintArgs {
+    fn default() -> Self {
+        Self {
+            price: PRICE,
+            bid_step: STEP,
+            reserve_price: None,
+            buy_now_price: None,
+            uri: "https://example.com/nft.json".to_string(),
+            creators: None,
+        }
+    }
+}
+
+pub struct Library {
+    pub name: String,
+    pub authority: Keypair,
+    pub protocol_fee_account: Pubkey,
+}
+
+impl Library {
+    pub fn address(&self) -> Pubkey {
+        pda(&[LIBRARY_SEEDS, self.name.as_bytes()])
+    }
+
+    pub fn mint_address(&self) -> Pubkey {
+        pda(&[LIBRARY_MINT_SEEDS, self.name.as_bytes()])
+    }
+
+    pub fn account_address(&self) -> Pubkey {
+        pda(&[LIBRARY_ACCOUNT_SEEDS, self.name.as_bytes()])
+    }
+
+    pub fn config_address(&self) -> Pubkey {
+        pda(&[LIBRARY_CONFIG_SEEDS, self.name.as_bytes()])
+    }
+
+    pub fn init(
+        &self,
+        seller_fee_basis_points: u16,
+        protocol_fee_basis_points: u16,
+        protocol_fee_account: Option<Pubkey>
+    ) -> Instruction {
+        let library_mint = self.mint_address();
+
+        instruction(
+            dapp::accounts::InitLibrary {
+                signer: self.authority.pubkey(),
+                library_mint,
+                library_account: self.account_address(),
+                library: self.address(),
+                library_config: self.config_address(),
+                library_metadata: metadata_pda(&library_mint),
+                master_edition: master_edition_pda(&library_mint),
+                system_program: system_program::ID,
+                token_program: token::ID,
+                metadata_program: Metadata::id(),
+                rent: sysvar::rent::ID,
+            },
+            dapp::instruction::InitLibrary {
+                name: self.name.clone(),
+                description: "Test library".to_string(),
+                url: "https://example.com".to_string(),
+                icon: "https://example.com/icon.png".to_string(),
+                uri: "https://example.com/library.json".to_string(),
+                symbol: None,
+                creators: None,
+                seller_fee_basis_points,
+                protocol_fee_basis_points,
+                protocol_fee_account,
+            },
+            &[]
+        )
+    }
+
+    pub fn mint(&self, name: &str, args: MintArgs) -> Instruction {
+        self.mint_with(name, args, None)
+    }
+
+    /// `payment_mint` without an escrow payment account, only useful to hit the mismatch check
+    pub fn mint_with(&self, name: &str, args: MintArgs, payment_mint: Option<Pubkey>) -> Instruction {
+        let lot = self.lot(name);
+        let library_mint = self.mint_address();
+        let nft_mint = lot.nft_mint();
+
+        instruction(
+            dapp::accounts::MintNft {
+                payer: self.authority.pubkey(),
+                nft_info: lot.nft_info(),
+                nft_mint,
+                library_account: self.account_address(),
+                library_mint,
+                library_metadata: metadata_pda(&library_mint),
+                library: self.address(),
+                library_config: self.config_address(),
+                nft_bidder_escrow: lot.escrow(),
+                library_master_edition: master_edition_pda(&library_mint),
+                nft_master_edition: master_edition_pda(&nft_mint),
+                nft_metadata: metadata_pda(&nft_mint),
+                associated_nft: lot.escrow_nft_account(),
+                payment_mint,
+                escrow_payment_account: None,
+                system_program: system_program::ID,
+                token_program: token::ID,
+                metadata_program: Metadata::id(),
+                associated_token_program: associated_token::ID,
+                rent: sysvar::rent::ID,
+            },
+            dapp::instruction::MintNft {
+                name: name.to_string(),
+                price: args.price,
+                library_name: self.name.clone(),
+                bid_step: args.bid_step,
+                reserve_price: args.reserve_price,
+                buy_now_price: args.buy_now_price,
+                uri: args.uri,
+                symbol: None,
+                creators: args.creators,
+            },
+            &[]
+        )
+    }
+
+    /// Passes the six accounts of every item after the fixed ones, in the order the program expects
+    pub fn batch_mint(&self, items: Vec<dapp::MintNftItem>) -> Instruction {
+        let library_mint = self.mint_address();
+        let item_accounts: Vec<AccountMeta> = items
+            .iter()
+            .flat_map(|item| {
+                let lot = self.lot(&item.name);
+
+                [
+                    lot.nft_info(),
+                    lot.nft_mint(),
+                    lot.escrow(),
+                    lot.escrow_nft_account(),
+                    metadata_pda(&lot.nft_mint()),
+                    master_edition_pda(&lot.nft_mint()),
+                ].map(|address| AccountMeta::new(address, false))
+            })
+            .collect();
+
+        instruction(
+            dapp::accounts::BatchMintNft {
+                payer: self.authority.pubkey(),
+                library_account: self.account_address(),
+                library_mint,
+                library_metadata: metadata_pda(&library_mint),
+                library_master_edition: master_edition_pda(&library_mint),
+                library: self.address(),
+                library_config: self.config_address(),
+                system_program: system_program::ID,
+                token_program: token::ID,
+                metadata_program: Metadata::id(),
+                associated_token_program: associated_token::ID,
+                rent: sysvar::rent::ID,
+            },
+            dapp::instruction::BatchMintNft {
+                library_name: self.name.clone(),
+                items,
+            },
+            &item_accounts
+        )
+    }
+
+    pub fn update(
+        &self,
+        authority: &Pubkey,
+        description: Option<String>,
+        delegates: Option<Vec<Pubkey>>
+    ) -> Instruction {
+        instruction(
+            dapp::accounts::UpdateLibrary {
+                authority: *authority,
+                library: self.address(),
+            },
+            dapp::instruction::UpdateLibrary {
+                name: self.name.clone(),
+                description,
+                url: None,
+                icon: None,
+                delegates,
+            },
+            &[]
+        )
+    }
+
+    pub fn lot(&self, name: &str) -> Lot {
+        Lot {
+            library: self.name.clone(),
+            name: name.to_string(),
+            seller: self.authority.pubkey(),
+            protocol_fee_account: self.protocol_fee_account,
+            mint: pda(&[NFT_MINT_SEEDS, self.name.as_bytes(), name.as_bytes()]),
+        }
+    }
+
+    /// A lot minted before library scoping, its mint keeps the unscoped `[name]` address
+    pub fn legacy_lot(&self, name: &str) -> Lot {
+        Lot {
+            mint: pda(&[name.as_bytes()]),
+            ..self.lot(name)
+        }
+    }
+
+    pub fn migrate(&self, signer: &Pubkey) -> Instruction {
+        let program_data = Pubkey::find_program_address(
+            &[dapp::ID.as_ref()],
+            &bpf_loader_upgradeable::ID
+        ).0;
+
+        instruction(
+            dapp::accounts::MigrateLibrary {
+                signer: *signer,
+                program_data,
+                library_mint: self.mint_address(),
+                library: self.address(),
+                library_config: self.config_address(),
+                system_program: system_program::ID,
+            },
+            dapp::instruction::MigrateLibrary {
+                name: self.name.clone(),
+                authority: self.authority.pubkey(),
+                seller_fee_basis_points: SELLER_FEE_BASIS_POINTS,
+                protocol_fee_basis_points: PROTOCOL_FEE_BASIS_POINTS,
+                protocol_fee_account: Some(self.protocol_fee_account),
+            },
+            &[]
+        )
+    }
+}
+
+/// One NFT of a library, with builders for every instruction of its auction
+pub struct Lot {
+    pub library: String,
+    pub name: String,
+    pub seller: Pubkey,
+    pub protocol_fee_account: Pubkey,
+    mint: Pubkey,
+}
+
+impl Lot {
+    fn seeds<'a>(&'a self, prefix: &'a [u8]) -> [&'a [u8]; 3] {
+        [prefix, self.library.as_bytes(), self.name.as_bytes()]
+    }
+
+    pub fn nft_info(&self) -> Pubkey {
+        pda(&self.seeds(BUID_NFT_SEEDS))
+    }
+
+    pub fn nft_mint(&self) -> Pubkey {
+        self.mint
+    }
+
+    pub fn auction_state(&self) -> Pubkey {
+        pda(&self.seeds(AUCTION_STATE_SEEDS))
+    }
+
+    pub fn escrow(&self) -> Pubkey {
+        pda(&self.seeds(BIDDER_ESCROW_SEEDS))
+    }
+
+    pub fn escrow_nft_account(&self) -> Pubkey {
+        get_associated_token_address(&self.escrow(), &self.nft_mint())
+    }
+
+    pub fn nft_account(&self, owner: &Pubkey) -> Pubkey {
+        get_associated_token_address(owner, &self.nft_mint())
+    }
+
+    pub fn pending_refund(&self, bidder: &Pubkey) -> Pubkey {
+        let [prefix, library, name] = self.seeds(PENDING_REFUND_SEEDS);
+
+        pda(&[prefix, library, name, bidder.as_ref()])
+    }
+
+    pub fn sealed_bid(&self, bidder: &Pubkey) -> Pubkey {
+        let [prefix, library, name] = self.seeds(SEALED_BID_SEEDS);
+
+        pda(&[prefix, library, name, bidder.as_ref()])
+    }
+
+    fn library_config(&self) -> Pubkey {
+        pda(&[LIBRARY_CONFIG_SEEDS, self.library.as_bytes()])
+    }
+
+    /// The seller is the only verified creator of every lot, royalties are paid to it
+    pub fn creator_accounts(&self) -> Vec<AccountMeta> {
+        vec![AccountMeta::new(self.seller, false)]
+    }
+
+    pub fn start(
+        &self,
+        start_time: Option<i64>,
+        end_time: i64,
+        extension_window: i64,
+        auction_type: AuctionType,
+        gate: Option<AuctionGate>
+    ) -> Instruction {
+        self.start_by(&self.seller, start_time, end_time, extension_window, auction_type, gate)
+    }
+
+    pub fn start_by(
+        &self,
+        seller: &Pubkey,
+        start_time: Option<i64>,
+        end_time: i64,
+        extension_window: i64,
+        auction_type: AuctionType,
+        gate: Option<AuctionGate>
+    ) -> Instruction {
+        instruction(
+            dapp::accounts::StartAuction {
+                seller: *seller,
+                nft_info: self.nft_info(),
+                auction_state: self.auction_state(),
+                library_account: pda(&[LIBRARY_ACCOUNT_SEEDS, self.library.as_bytes()]),
+                system_program: system_program::ID,
+            },
+            dapp::instruction::StartAuction {
+                name: self.name.clone(),
+                library_name: self.library.clone(),
+                start_time,
+                end_time,
+                extension_window,
+                auction_type,
+                gate,
+            },
+            &[]
+        )
+    }
+
+    pub fn reschedule(&self, start_time: Option<i64>, end_time: i64) -> Instruction {
+        instruction(
+            dapp::accounts::RescheduleAuction {
+                seller: self.seller,
+                auction_state: self.auction_state(),
+            },
+            dapp::instruction::RescheduleAuction {
+                name: self.name.clone(),
+                library_name: self.library.clone(),
+                start_time,
+                end_time,
+            },
+            &[]
+        )
+    }
+
+    pub fn bid_accounts(&self, bidder: &Pubkey) -> dapp::accounts::BidNft {
+        let library_mint = pda(&[LIBRARY_MINT_SEEDS, self.library.as_bytes()]);
+
+        dapp::accounts::BidNft {
+            payer: *bidder,
+            nft_info: self.nft_info(),
+            auction_state: self.auction_state(),
+            nft_mint: self.nft_mint(),
+            nft_bidder_escrow: self.escrow(),
+            library_account: pda(&[LIBRARY_ACCOUNT_SEEDS, self.library.as_bytes()]),
+            pending_refund: self.pending_refund(bidder),
+            seller: self.seller,
+            rent_payer: self.seller,
+            from_token_account: self.escrow_nft_account(),
+            to_token_account: self.nft_account(bidder),
+            library_mint,
+            library_config: self.library_config(),
+            nft_metadata: metadata_pda(&self.nft_mint()),
+            protocol_fee_account: Some(self.protocol_fee_account),
+            gate_token_account: None,
+            gate_metadata: None,
+            payment_mint: None,
+            escrow_payment_account: None,
+            payer_payment_account: None,
+            seller_payment_account: None,
+            protocol_fee_payment_account: None,
+            system_program: system_program::ID,
+            token_program: token::ID,
+            metadata_program: Metadata::id(),
+            associated_token_program: associated_token::ID,
+            rent: sysvar::rent::ID,
+        }
+    }
+
+    pub fn bid(&self, bidder: &Pubkey, bid_amount: u64) -> Instruction {
+        self.bid_with(self.bid_accounts(bidder), bid_amount, None)
+    }
+
+    pub fn bid_with(
+        &self,
+        accounts: dapp::accounts::BidNft,
+        bid_amount: u64,
+        merkle_proof: Option<Vec<[u8; 32]>>
+    ) -> Instruction {
+        instruction(
+            accounts,
+            dapp::instruction::BidNft {
+                name: self.name.clone(),
+                library_name: self.library.clone(),
+                bid_amount,
+                merkle_proof,
+            },
+            // A Dutch bid settles on the spot and pays the creators
+            &self.creator_accounts()
+        )
+    }
+
+    pub fn buy_now(&self, buyer: &Pubkey) -> Instruction {
+        instruction(
+            dapp::accounts::BuyNow {
+                payer: *buyer,
+                nft_info: self.nft_info(),
+                auction_state: self.auction_state(),
+                nft_mint: self.nft_mint(),
+                nft_bidder_escrow: self.escrow(),
+                pending_refund: self.pending_refund(buyer),
+                seller: self.seller,
+                rent_payer: self.seller,
+                from_token_account: self.escrow_nft_account(),
+                to_token_account: self.nft_account(buyer),
+                library_config: self.library_config(),
+                nft_metadata: metadata_pda(&self.nft_mint()),
+                protocol_fee_account: Some(self.protocol_fee_account),
+                gate_token_account: None,
+                gate_metadata: None,
+                payment_mint: None,
+                escrow_payment_account: None,
+                payer_payment_account: None,
+                seller_payment_account: None,
+                protocol_fee_payment_account: None,
+                token_program: token::ID,
+                metadata_program: Metadata::id(),
+                system_program: system_program::ID,
+                associated_token_program: associated_token::ID,
+                rent: sysvar::rent::ID,
+            },
+            dapp::instruction::BuyNow {
+                name: self.name.clone(),
+                library_name: self.library.clone(),
+                merkle_proof: None,
+            },
+            &self.creator_accounts()
+        )
+    }
+
+    pub fn commit(&self, bidder: &Pubkey, amount: u64, salt: [u8; 32], deposit: u64) -> Instruction {
+        instruction(
+            dapp::accounts::CommitBid {
+                payer: *bidder,
+                nft_info: self.nft_info(),
+                auction_state: self.auction_state(),
+                nft_bidder_escrow: self.escrow(),
+                pending_refund: self.pending_refund(bidder),
+                sealed_bid: self.sealed_bid(bidder),
+                gate_token_account: None,
+                gate_metadata: None,
+                payment_mint: None,
+                escrow_payment_account: None,
+                payer_payment_account: None,
+                token_program: token::ID,
+                system_program: system_program::ID,
+            },
+            dapp::instruction::CommitBid {
+                name: self.name.clone(),
+                library_name: self.library.clone(),
+                commitment: SealedBid::commitment(amount, &salt, bidder),
+                deposit,
+                merkle_proof: None,
+            },
+            &[]
+        )
+    }
+
+    pub fn reveal(&self, bidder: &Pubkey, amount: u64, salt: [u8; 32]) -> Instruction {
+        instruction(
+            dapp::accounts::RevealBid {
+                bidder: *bidder,
+                nft_info: self.nft_info(),
+                auction_state: self.auction_state(),
+                pending_refund: self.pending_refund(bidder),
+                sealed_bid: self.sealed_bid(bidder),
+            },
+            dapp::instruction::RevealBid {
+                name: self.name.clone(),
+                library_name: self.library.clone(),
+                amount,
+                salt,
+            },
+            &[]
+        )
+    }
+
+    pub fn transfer(&self, recipient: &Pubkey) -> Instruction {
+        let nft_mint = self.nft_mint();
+
+        instruction(
+            dapp::accounts::TransferNft {
+                owner: self.seller,
+                recipient: *recipient,
+                rent_payer: self.seller,
+                nft_mint,
+                from_token_account: self.escrow_nft_account(),
+                to_token_account: self.nft_account(recipient),
+                nft_info: self.nft_info(),
+                nft_bidder_escrow: self.escrow(),
+                auction_state: self.auction_state(),
+                library_config: self.library_config(),
+                nft_metadata: metadata_pda(&nft_mint),
+                winner_refund: self.winner_refund(recipient),
+                protocol_fee_account: Some(self.protocol_fee_account),
+                payment_mint: None,
+                escrow_payment_account: None,
+                owner_payment_account: None,
+                protocol_fee_payment_account: None,
+                token_program: token::ID,
+                metadata_program: Metadata::id(),
+                system_program: system_program::ID,
+                associated_token_program: associated_token::ID,
+                rent: sysvar::rent::ID,
+            },
+            dapp::instruction::TransferNft {
+                name: self.name.clone(),
+                library_name: self.library.clone(),
+            },
+            &self.creator_accounts()
+        )
+    }
+
+    /// Nobody has locked funds when the seller still holds the winning slot
+    fn winner_refund(&self, recipient: &Pubkey) -> Option<Pubkey> {
+        (*recipient != self.seller).then(|| self.pending_refund(recipient))
+    }
+
+    pub fn settle_accounts(&self, payer: &Pubkey, recipient: &Pubkey) -> dapp::accounts::SettleAuction {
+        let nft_mint = self.nft_mint();
+
+        dapp::accounts::SettleAuction {
+            payer: *payer,
+            seller: self.seller,
+            recipient: *recipient,
+            rent_payer: self.seller,
+            nft_mint,
+            from_token_account: self.escrow_nft_account(),
+            to_token_account: self.nft_account(recipient),
+            nft_info: self.nft_info(),
+            nft_bidder_escrow: self.escrow(),
+            auction_state: self.auction_state(),
+            library_config: self.library_config(),
+            nft_metadata: metadata_pda(&nft_mint),
+            winner_refund: self.winner_refund(recipient),
+            protocol_fee_account: Some(self.protocol_fee_account),
+            payment_mint: None,
+            escrow_payment_account: None,
+            seller_payment_account: None,
+            protocol_fee_payment_account: None,
+            token_program: token::ID,
+            metadata_program: Metadata::id(),
+            system_program: system_program::ID,
+            associated_token_program: associated_token::ID,
+            rent: sysvar::rent::ID,
+        }
+    }
+
+    pub fn settle(&self, payer: &Pubkey, recipient: &Pubkey) -> Instruction {
+        self.settle_with(self.settle_accounts(payer, recipient), &self.creator_accounts())
+    }
+
+    pub fn settle_with(
+        &self,
+        accounts: dapp::accounts::SettleAuction,
+        creator_accounts: &[AccountMeta]
+    ) -> Instruction {
+        instruction(
+            accounts,
+            dapp::instruction::SettleAuction {
+                name: self.name.clone(),
+                library_name: self.library.clone(),
+            },
+            creator_accounts
+        )
+    }
+
+    pub fn cancel(&self, seller: &Pubkey) -> Instruction {
+        instruction(
+            dapp::accounts::CancelAuction {
+                seller: *seller,
+                nft_info: self.nft_info(),
+                auction_state: self.auction_state(),
+            },
+            dapp::instruction::CancelAuction {
+                name: self.name.clone(),
+                library_name: self.library.clone(),
+            },
+            &[]
+        )
+    }
+
+    pub fn withdraw_refund(&self, bidder: &Pubkey) -> Instruction {
+        instruction(
+            dapp::accounts::WithdrawRefund {
+                bidder: *bidder,
+                pending_refund: self.pending_refund(bidder),
+                sealed_bid: None,
+                nft_bidder_escrow: self.escrow(),
+                nft_info: self.nft_info(),
+                rent_payer: self.seller,
+                payment_mint: None,
+                escrow_payment_account: None,
+                bidder_payment_account: None,
+                token_program: token::ID,
+                system_program: system_program::ID,
+            },
+            dapp::instruction::WithdrawRefund {
+                name: self.name.clone(),
+                library_name: self.library.clone(),
+            },
+            &[]
+        )
+    }
+
+    pub fn relist(&self, end_time: i64, starting_price: Option<u64>) -> Instruction {
+        instruction(
+            dapp::accounts::Relist {
+                seller: self.seller,
+                nft_info: self.nft_info(),
+                auction_state: self.auction_state(),
+            },
+            dapp::instruction::Relist {
+                name: self.name.clone(),
+                library_name: self.library.clone(),
+                end_time,
+                starting_price,
+            },
+            &[]
+        )
+    }
+
+    /// Moves a legacy lot to its library-scoped `NftInfo`, signed by the seller who paid its rent
+    pub fn migrate(&self) -> Instruction {
+        self.migrate_with(false, None)
+    }
+
+    /// Same as `migrate` for a lot whose legacy auction is still running, `legacy_bidder` leads it
+    pub fn migrate_with(&self, running_auction: bool, legacy_bidder: Option<&Pubkey>) -> Instruction {
+        instruction(
+            dapp::accounts::MigrateNft {
+                payer: self.seller,
+                legacy_nft_info: pda(&[BUID_NFT_SEEDS, self.name.as_bytes()]),
+                legacy_auction_state: pda(&[AUCTION_STATE_SEEDS, self.name.as_bytes()]),
+                nft_info: self.nft_info(),
+                auction_state: running_auction.then(|| self.auction_state()),
+                legacy_bidder: legacy_bidder.copied(),
+                bidder_refund: legacy_bidder.map(|bidder| self.pending_refund(bidder)),
+                nft_mint: self.nft_mint(),
+                nft_bidder_escrow: self.escrow(),
+                system_program: system_program::ID,
+            },
+            dapp::instruction::MigrateNft {
+                name: self.name.clone(),
+                library_name: self.library.clone(),
+            },
+            &[]
+        )
+    }
+
+    pub fn withdraw_unsold(&self) -> Instruction {
+        instruction(
+            dapp::accounts::WithdrawUnsold {
+                seller: self.seller,
+                nft_info: self.nft_info(),
+                auction_state: self.auction_state(),
+                nft_bidder_escrow: self.escrow(),
+                rent_payer: self.seller,
+                nft_mint: self.nft_mint(),
+                from_token_account: self.escrow_nft_account(),
+                to_token_account: self.nft_account(&self.seller),
+                payment_mint: None,
+                escrow_payment_account: None,
+                token_program: token::ID,
+                associated_token_program: associated_token::ID,
+                system_program: system_program::ID,
+            },
+            dapp::instruction::WithdrawUnsold {
+                name: self.name.clone(),
+                library_name: self.library.clone(),
+            },
+            &[]
+        )
+    }
+}
@@ -0,0 +1,11 @@
+//! In-process tests of the dapp program on LiteSVM, no validator needed.
+//!
+//! The dapp program is the SBF binary from `anchor build`, `DAPP_SO` points at another build.
+//! Set `DAPP_NATIVE=1` to run it through `native` where no SBF toolchain is available, that
+//! skips compute metering and the SBF memory checks for the dapp itself. The Metaplex token
+//! metadata program is the committed `tests/fixtures/mpl_token_metadata.so`.
+
+mod errors;
+mod flow;
+mod harness;
+mod native;
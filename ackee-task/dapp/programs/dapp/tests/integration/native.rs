@@ -0,0 +1,390 @@
+//! Runs the dapp program as a LiteSVM builtin compiled for the host, so the suite needs no SBF
+//! toolchain. Adapted from the `processor!` support in `solana-program-test`: the instruction is
+//! serialized with the SBF ABI, handed to `dapp::entry`, and the syscalls the program makes
+//! (CPI, sysvars, logs, return data) are routed back into the running `InvokeContext`.
+
+use std::{
+    cell::RefCell,
+    collections::{ HashMap, HashSet },
+    mem::transmute,
+    panic::AssertUnwindSafe,
+    sync::{ Arc, Once },
+};
+
+use anchor_lang::solana_program::{
+    account_info::AccountInfo,
+    entrypoint::{ deserialize, ProgramResult, SUCCESS },
+    instruction::{ Instruction, InstructionError },
+    program_error::{ ProgramError, UNSUPPORTED_SYSVAR },
+    program_stubs::{ set_syscall_stubs, SyscallStubs },
+    pubkey::Pubkey,
+    stable_layout::stable_instruction::StableInstruction,
+    sysvar::Sysvar,
+};
+use litesvm::LiteSVM;
+use solana_account::Account;
+use solana_program_runtime::{
+    invoke_context::{ BuiltinFunctionWithContext, InvokeContext },
+    serialization::serialize_parameters,
+    stable_log,
+};
+use solana_sbpf::{
+    error::EbpfError,
+    vm::{ get_runtime_environment_key, ContextObject, EbpfVm },
+};
+use solana_sdk_ids::native_loader;
+use solana_timings::ExecuteTimings;
+use solana_transaction_context::IndexOfAccount;
+
+thread_local! {
+    static INVOKE_CONTEXT: RefCell<Option<usize>> = const { RefCell::new(None) };
+}
+
+static STUBS: Once = Once::new();
+
+/// Registers the dapp program as a native builtin
+pub fn add_dapp(svm: &mut LiteSVM) {
+    STUBS.call_once(|| {
+        set_syscall_stubs(Box::new(NativeSyscalls));
+    });
+
+    svm.add_builtin(dapp::ID, dapp_builtin());
+
+    // `add_builtin` leaves the account with the BPF loader, which refuses to run builtins
+    svm.set_account(dapp::ID, Account {
+        lamports: 1,
+        data: vec![],
+        owner: native_loader::ID,
+        executable: true,
+        rent_epoch: 0,
+    }).unwrap();
+}
+
+fn dapp_builtin() -> BuiltinFunctionWithContext {
+    |vm, _arg0, _arg1, _arg2, _arg3, _arg4| {
+        let vm = unsafe {
+            &mut *(
+                (vm as *mut u64).offset(-(get_runtime_environment_key() as isize)) as *mut EbpfVm<
+                    InvokeContext
+                >
+            )
+        };
+
+        vm.program_result = invoke(vm.context_object_pointer)
+            .map_err(EbpfError::SyscallError)
+            .into();
+    }
+}
+
+// Anchor's `entry` ties the slice lifetime to the account lifetime, the parameter buffer behind
+// both outlives the call
+fn entry(program_id: &Pubkey, accounts: &[AccountInfo], data: &[u8]) -> ProgramResult {
+    let accounts = unsafe { transmute::<&[AccountInfo], &[AccountInfo]>(accounts) };
+
+    dapp::entry(program_id, accounts, data)
+}
+
+fn set_invoke_context(new: &mut InvokeContext) {
+    INVOKE_CONTEXT.with(|invoke_context| {
+        invoke_context.replace(Some(new as *mut InvokeContext as usize));
+    });
+}
+
+fn get_invoke_context<'a, 'b>() -> &'a mut InvokeContext<'b> {
+    let ptr = INVOKE_CONTEXT.with(|invoke_context| {
+        invoke_context.borrow().expect("no instruction is being processed")
+    });
+
+    unsafe { &mut *(ptr as *mut InvokeContext) }
+}
+
+fn invoke(invoke_context: &mut InvokeContext) -> Result<u64, Box<dyn std::error::Error>> {
+    set_invoke_context(invoke_context);
+
+    let transaction_context = &invoke_context.transaction_context;
+    let instruction_context = transaction_context.get_current_instruction_context()?;
+    let deduplicated_indices: HashSet<IndexOfAccount> = (
+        0..instruction_context.get_number_of_instruction_accounts()
+    ).collect();
+
+    // A builtin must consume compute units
+    invoke_context.consume_checked(1)?;
+
+    let log_collector = invoke_context.get_log_collector();
+    let program_id = instruction_context.get_last_program_key(transaction_context)?;
+    stable_log::program_invoke(&log_collector, program_id, invoke_context.get_stack_height());
+
+    let mask_out_rent_epoch = invoke_context.get_feature_set().mask_out_rent_epoch_in_vm_serialization;
+    let (mut parameter_bytes, _regions, _account_lengths) = serialize_parameters(
+        transaction_context,
+        instruction_context,
+        true,
+        mask_out_rent_epoch
+    )?;
+    let (program_id, account_infos, input) = unsafe {
+        deserialize(&mut parameter_bytes.as_slice_mut()[0] as *mut u8)
+    };
+
+    match std::panic::catch_unwind(AssertUnwindSafe(|| entry(program_id, &account_infos, input))) {
+        Ok(Ok(())) => stable_log::program_success(&log_collector, program_id),
+        Ok(Err(program_error)) => {
+            let err = InstructionError::from(u64::from(program_error));
+            stable_log::program_failure(&log_collector, program_id, &err);
+
+            return Err(Box::new(err));
+        }
+        Err(_panic) => {
+            let err = InstructionError::ProgramFailedToComplete;
+            stable_log::program_failure(&log_collector, program_id, &err);
+
+            return Err(Box::new(err));
+        }
+    }
+
+    let account_info_map: HashMap<_, _> = account_infos
+        .iter()
+        .map(|account_info| (account_info.key, account_info))
+        .collect();
+
+    // A CPI may have replaced the instruction context, fetch it again
+    let transaction_context = &invoke_context.transaction_context;
+    let instruction_context = transaction_context.get_current_instruction_context()?;
+
+    for index in deduplicated_indices {
+        let mut borrowed_account = instruction_context.try_borrow_instruction_account(
+            transaction_context,
+            index
+        )?;
+
+        if !borrowed_account.is_writable() {
+            continue;
+        }
+
+        let Some(account_info) = account_info_map.get(borrowed_account.get_key()) else {
+            continue;
+        };
+
+        if borrowed_account.get_lamports() != account_info.lamports() {
+            borrowed_account.set_lamports(account_info.lamports())?;
+        }
+
+        if borrowed_account.can_data_be_resized(account_info.data_len()).is_ok() {
+            borrowed_account.set_data_from_slice(&account_info.data.borrow())?;
+        }
+
+        if borrowed_account.get_owner() != account_info.owner {
+            borrowed_account.set_owner(account_info.owner.as_ref())?;
+        }
+    }
+
+    Ok(0)
+}
+
+fn get_sysvar<T: Sysvar + Clone>(sysvar: Result<Arc<T>, InstructionError>, var_addr: *mut u8) -> u64 {
+    let invoke_context = get_invoke_context();
+    let cost = invoke_context.get_execution_cost().sysvar_base_cost + (T::size_of() as u64);
+
+    if invoke_context.consume_checked(cost).is_err() {
+        panic!("Exceeded compute budget");
+    }
+
+    match sysvar {
+        Ok(sysvar) => {
+            unsafe {
+                *(var_addr as *mut T) = T::clone(&sysvar);
+            }
+
+            SUCCESS
+        }
+        Err(_) => UNSUPPORTED_SYSVAR,
+    }
+}
+
+fn program_error(err: InstructionError) -> ProgramError {
+    ProgramError::try_from(err).unwrap_or_else(|err| panic!("{}", err))
+}
+
+struct NativeSyscalls;
+
+impl SyscallStubs for NativeSyscalls {
+    fn sol_log(&self, message: &str) {
+        stable_log::program_log(&get_invoke_context().get_log_collector(), message);
+    }
+
+    fn sol_log_data(&self, fields: &[&[u8]]) {
+        stable_log::program_data(&get_invoke_context().get_log_collector(), fields);
+    }
+
+    fn sol_invoke_signed(
+        &self,
+        instruction: &Instruction,
+        account_infos: &[AccountInfo],
+        signers_seeds: &[&[&[u8]]]
+    ) -> ProgramResult {
+        let instruction = StableInstruction::from(instruction.clone());
+        let invoke_context = get_invoke_context();
+        let log_collector = invoke_context.get_log_collector();
+        let transaction_context = &invoke_context.transaction_context;
+        let instruction_context = transaction_context
+            .get_current_instruction_context()
+            .map_err(program_error)?;
+        let caller = instruction_context
+            .get_last_program_key(transaction_context)
+            .map_err(program_error)?;
+
+        stable_log::program_invoke(
+            &log_collector,
+            &instruction.program_id,
+            invoke_context.get_stack_height()
+        );
+
+        let signers = signers_seeds
+            .iter()
+            .map(|seeds| Pubkey::create_program_address(seeds, caller).unwrap())
+            .collect::<Vec<_>>();
+        let (instruction_accounts, program_indices) = invoke_context
+            .prepare_instruction(&instruction, &signers)
+            .map_err(program_error)?;
+
+        // Hand the caller's AccountInfo changes to the callee
+        let transaction_context = &invoke_context.transaction_context;
+        let instruction_context = transaction_context
+            .get_current_instruction_context()
+            .map_err(program_error)?;
+        let mut account_indices = Vec::with_capacity(instruction_accounts.len());
+
+        for instruction_account in &instruction_accounts {
+            let account_key = transaction_context
+                .get_key_of_account_at_index(instruction_account.index_in_transaction)
+                .map_err(program_error)?;
+            let account_info_index = account_infos
+                .iter()
+                .position(|account_info| account_info.key == account_key)
+                .ok_or(ProgramError::NotEnoughAccountKeys)?;
+            let account_info = &account_infos[account_info_index];
+            let mut borrowed_account = instruction_context
+                .try_borrow_instruction_account(
+                    transaction_context,
+                    instruction_account.index_in_caller
+                )
+                .map_err(program_error)?;
+
+            if borrowed_account.get_lamports() != account_info.lamports() {
+                borrowed_account.set_lamports(account_info.lamports()).map_err(program_error)?;
+            }
+
+            let account_info_data = account_info.try_borrow_data()?;
+
+            match borrowed_account.can_data_be_resized(account_info_data.len()) {
+                Ok(()) =>
+                    borrowed_account.set_data_from_slice(&account_info_data).map_err(program_error)?,
+                Err(err) if borrowed_account.get_data() != *account_info_data => {
+                    panic!("{err:?}");
+                }
+                _ => {}
+            }
+
+            // The owner goes last so the lamports and data above may still change
+            if borrowed_account.get_owner() != account_info.owner {
+                borrowed_account.set_owner(account_info.owner.as_ref()).map_err(program_error)?;
+            }
+
+            if instruction_account.is_writable {
+                account_indices.push((instruction_account.index_in_caller, account_info_index));
+            }
+        }
+
+        let mut compute_units_consumed = 0;
+        invoke_context
+            .process_instruction(
+                &instruction.data,
+                &instruction_accounts,
+                &program_indices,
+                &mut compute_units_consumed,
+                &mut ExecuteTimings::default()
+            )
+            .map_err(program_error)?;
+
+        // Hand the callee's changes back to the caller's AccountInfos
+        let transaction_context = &invoke_context.transaction_context;
+        let instruction_context = transaction_context
+            .get_current_instruction_context()
+            .map_err(program_error)?;
+
+        for (index_in_caller, account_info_index) in account_indices {
+            let borrowed_account = instruction_context
+                .try_borrow_instruction_account(transaction_context, index_in_caller)
+                .map_err(program_error)?;
+            let account_info = &account_infos[account_info_index];
+
+            **account_info.try_borrow_mut_lamports()? = borrowed_account.get_lamports();
+
+            if account_info.owner != borrowed_account.get_owner() {
+                // The system program assigns accounts during CPI, AccountInfo has no setter for it
+                #[allow(invalid_reference_casting)]
+                unsafe {
+                    *(account_info.owner as *const Pubkey as *mut Pubkey) =
+                        *borrowed_account.get_owner();
+                }
+            }
+
+            let new_data = borrowed_account.get_data();
+
+            if account_info.data_len() != new_data.len() {
+                account_info.resize(new_data.len())?;
+            }
+
+            account_info.try_borrow_mut_data()?.clone_from_slice(new_data);
+        }
+
+        stable_log::program_success(&log_collector, &instruction.program_id);
+
+        Ok(())
+    }
+
+    fn sol_get_clock_sysvar(&self, var_addr: *mut u8) -> u64 {
+        get_sysvar(get_invoke_context().get_sysvar_cache().get_clock(), var_addr)
+    }
+
+    fn sol_get_epoch_schedule_sysvar(&self, var_addr: *mut u8) -> u64 {
+        get_sysvar(get_invoke_context().get_sysvar_cache().get_epoch_schedule(), var_addr)
+    }
+
+    fn sol_get_epoch_rewards_sysvar(&self, var_addr: *mut u8) -> u64 {
+        get_sysvar(get_invoke_context().get_sysvar_cache().get_epoch_rewards(), var_addr)
+    }
+
+    fn sol_get_rent_sysvar(&self, var_addr: *mut u8) -> u64 {
+        get_sysvar(get_invoke_context().get_sysvar_cache().get_rent(), var_addr)
+    }
+
+    fn sol_get_last_restart_slot(&self, var_addr: *mut u8) -> u64 {
+        get_sysvar(get_invoke_context().get_sysvar_cache().get_last_restart_slot(), var_addr)
+    }
+
+    fn sol_get_return_data(&self) -> Option<(Pubkey, Vec<u8>)> {
+        let (program_id, data) = get_invoke_context().transaction_context.get_return_data();
+
+        Some((*program_id, data.to_vec()))
+    }
+
+    fn sol_set_return_data(&self, data: &[u8]) {
+        let transaction_context = &mut get_invoke_context().transaction_context;
+        let caller = *transaction_context
+            .get_current_instruction_context()
+            .and_then(|instruction_context| {
+                instruction_context.get_last_program_key(transaction_context)
+            })
+            .unwrap();
+
+        transaction_context.set_return_data(caller, data.to_vec()).unwrap();
+    }
+
+    fn sol_get_stack_height(&self) -> u64 {
+        get_invoke_context().get_stack_height() as u64
+    }
+
+    fn sol_remaining_compute_units(&self) -> u64 {
+        get_invoke_context().get_remaining()
+    }
+}
@@ -4,21 +4,66 @@ import { PublicKey } from "@solana/web3.js";
 
 import { Dapp } from "../target/types/dapp";
 import { provider } from "./anchor_provider";
-import { TOKEN_PROGRAM_ID } from "@solana/spl-token";
+import {
+  TOKEN_PROGRAM_ID,
+  getAssociatedTokenAddressSync,
+} from "@solana/spl-token";
 
-const [, , nftName] = process.argv; // Default to "Test" if not provided
+const [, , nftName, libraryName, bidAmount] = process.argv; // Default to "Test" if not provided
 
-if (!nftName) {
-  console.error("Please provide nft name");
+if (!nftName || !libraryName || !bidAmount) {
+  console.error("Please provide nft name, library name and bid amount");
   process.exit(1);
 }
 
-let nftInfoPDA = (name: string) =>
+let auctionStatePDA = (libraryName: string, name: string) =>
+  PublicKey.findProgramAddressSync(
+    [
+      Buffer.from(
+        JSON.parse(
+          IDL.constants.find((c) => c.name === "AUCTION_STATE_SEEDS").value
+        )
+      ),
+      Buffer.from(libraryName),
+      Buffer.from(name),
+    ],
+    new PublicKey(IDL.address)
+  );
+
+let escrowPDA = (libraryName: string, name: string) =>
+  PublicKey.findProgramAddressSync(
+    [
+      Buffer.from(
+        JSON.parse(
+          IDL.constants.find((c) => c.name === "BIDDER_ESCROW_SEEDS").value
+        )
+      ),
+      Buffer.from(libraryName),
+      Buffer.from(name),
+    ],
+    new PublicKey(IDL.address)
+  );
+
+let libraryConfigPDA = (libraryName: string) =>
+  PublicKey.findProgramAddressSync(
+    [
+      Buffer.from(
+        JSON.parse(
+          IDL.constants.find((c) => c.name === "LIBRARY_CONFIG_SEEDS").value
+        )
+      ),
+      Buffer.from(libraryName),
+    ],
+    new PublicKey(IDL.address)
+  );
+
+let nftInfoPDA = (libraryName: string, name: string) =>
   PublicKey.findProgramAddressSync(
     [
       Buffer.from(
         JSON.parse(IDL.constants.find((c) => c.name === "BUID_NFT_SEEDS").value)
       ),
+      Buffer.from(libraryName),
       Buffer.from(name),
     ],
     new PublicKey(IDL.address)
@@ -29,16 +74,50 @@ const program = workspace.dapp as Program<Dapp>;
 
 (async () => {
   const nftInfoAccount = await program.account.nftInfo.fetch(
-    nftInfoPDA(nftName)[0]
+    nftInfoPDA(libraryName, nftName)[0]
+  );
+
+  const auctionStateAccount = await program.account.auctionState.fetch(
+    auctionStatePDA(libraryName, nftName)[0]
+  );
+  const escrowAccount = await program.account.escrowBidder.fetch(
+    escrowPDA(nftInfoAccount.libraryName, nftName)[0]
+  );
+  const libraryConfigAccount = await program.account.libraryConfig.fetch(
+    libraryConfigPDA(nftInfoAccount.libraryName)[0]
   );
 
   const bidNftTx = await program.methods
-    .bidNft(nftName)
+    .bidNft(
+      nftName,
+      libraryName,
+      new BN(Number(bidAmount)),
+      // Ungated auction, no allowlist proof
+      null
+    )
     .accounts({
-      previousBidder: nftInfoAccount.currentBidder,
+      seller: auctionStateAccount.seller,
+      rentPayer: escrowAccount.payer,
+      protocolFeeAccount: libraryConfigAccount.protocolFeeAccount,
+      toTokenAccount: getAssociatedTokenAddressSync(
+        nftInfoAccount.nftMint,
+        wallet.payer.publicKey
+      ),
       payer: wallet.payer.publicKey,
+      gateTokenAccount: null,
+      gateMetadata: null,
+      // Lamport-priced auction, no SPL payment accounts
+      paymentMint: null,
+      escrowPaymentAccount: null,
+      payerPaymentAccount: null,
+      sellerPaymentAccount: null,
+      protocolFeePaymentAccount: null,
       tokenProgram: TOKEN_PROGRAM_ID,
     })
+    // Royalties to the minter when a Dutch bid settles the auction
+    .remainingAccounts([
+      { pubkey: escrowAccount.payer, isWritable: true, isSigner: false },
+    ])
     .signers([wallet.payer])
     .instruction();
 
@@ -6,10 +6,21 @@ import { provider } from "./anchor_provider";
 const { wallet } = provider;
 const program = workspace.dapp as Program<Dapp>;
 
-const [, , name] = process.argv; // Default to "Test" if not provided
+const [
+  ,
+  ,
+  name,
+  uri,
+  description,
+  url,
+  icon,
+  sellerFeeBasisPoints,
+  protocolFeeBasisPoints,
+  protocolFeeAccount,
+] = process.argv; // Default to "Test" if not provided
 
-if (!name) {
-  console.error("Please provide name");
+if (!name || !uri) {
+  console.error("Please provide name and metadata uri");
   process.exit(1);
 }
 
@@ -19,7 +30,18 @@ if (!name) {
   console.log("====================\n");
 
   const initLoteryTx = await program.methods
-    .initLibrary(name)
+    .initLibrary(
+      name,
+      description ?? "",
+      url ?? "",
+      icon ?? "",
+      uri,
+      null, // Default symbol
+      null, // The library mint is the only creator
+      Number(sellerFeeBasisPoints ?? 0),
+      Number(protocolFeeBasisPoints ?? 0),
+      protocolFeeAccount ? new web3.PublicKey(protocolFeeAccount) : null
+    )
     .accounts({
       tokenProgram: TOKEN_PROGRAM_ID,
     })
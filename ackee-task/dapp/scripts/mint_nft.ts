@@ -8,10 +8,22 @@ const computeBudgetIx = ComputeBudgetProgram.setComputeUnitLimit({
   units: 400_000, // Start with 400k, increase if needed
 });
 
-const [, , nftName, price, libraryName, bidStep] = process.argv; // Default to "Test" if not provided
+const [
+  ,
+  ,
+  nftName,
+  price,
+  libraryName,
+  bidStep,
+  uri,
+  reservePrice,
+  buyNowPrice,
+] = process.argv; // Default to "Test" if not provided
 
-if (!nftName || !price || !libraryName || !bidStep) {
-  console.error("Please provide nft name, price, library name and bid step");
+if (!nftName || !price || !libraryName || !bidStep || !uri) {
+  console.error(
+    "Please provide nft name, price, library name, bid step and metadata uri"
+  );
   process.exit(1);
 }
 
@@ -24,10 +36,18 @@ const program = workspace.dapp as Program<Dapp>;
       nftName,
       new BN(Number(price)),
       libraryName,
-      new BN(Number(bidStep))
+      new BN(Number(bidStep)),
+      reservePrice ? new BN(Number(reservePrice)) : null,
+      buyNowPrice ? new BN(Number(buyNowPrice)) : null,
+      uri,
+      null, // Default symbol
+      null // The minter is the only creator
     )
     .accounts({
       payer: wallet.payer.publicKey,
+      // Lamport-priced auction, no SPL payment accounts
+      paymentMint: null,
+      escrowPaymentAccount: null,
       tokenProgram: TOKEN_PROGRAM_ID,
     })
     .signers([wallet.payer])
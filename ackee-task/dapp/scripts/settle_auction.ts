@@ -0,0 +1,109 @@
+import { web3, workspace, Program } from "@coral-xyz/anchor";
+import IDL from "../target/idl/dapp.json";
+import { PublicKey } from "@solana/web3.js";
+import { TOKEN_PROGRAM_ID } from "@solana/spl-token";
+
+import { Dapp } from "../target/types/dapp";
+import { provider } from "./anchor_provider";
+
+const [, , nftName, libraryName] = process.argv;
+
+if (!nftName || !libraryName) {
+  console.error("Please provide nft name and library name");
+  process.exit(1);
+}
+
+const seed = (name: string) =>
+  Buffer.from(JSON.parse(IDL.constants.find((c) => c.name === name).value));
+
+const programId = new PublicKey(IDL.address);
+
+const { wallet } = provider;
+const program = workspace.dapp as Program<Dapp>;
+
+(async () => {
+  const [nftInfoPDA] = PublicKey.findProgramAddressSync(
+    [seed("BUID_NFT_SEEDS"), Buffer.from(libraryName), Buffer.from(nftName)],
+    programId
+  );
+  const [auctionStatePDA] = PublicKey.findProgramAddressSync(
+    [
+      seed("AUCTION_STATE_SEEDS"),
+      Buffer.from(libraryName),
+      Buffer.from(nftName),
+    ],
+    programId
+  );
+
+  const nftInfoAccount = await program.account.nftInfo.fetch(nftInfoPDA);
+  const auctionStateAccount = await program.account.auctionState.fetch(
+    auctionStatePDA
+  );
+  const [escrowPDA] = PublicKey.findProgramAddressSync(
+    [
+      seed("BIDDER_ESCROW_SEEDS"),
+      Buffer.from(nftInfoAccount.libraryName),
+      Buffer.from(nftName),
+    ],
+    programId
+  );
+  const escrowAccount = await program.account.escrowBidder.fetch(escrowPDA);
+  const [libraryConfigPDA] = PublicKey.findProgramAddressSync(
+    [seed("LIBRARY_CONFIG_SEEDS"), Buffer.from(nftInfoAccount.libraryName)],
+    programId
+  );
+  const libraryConfigAccount = await program.account.libraryConfig.fetch(
+    libraryConfigPDA
+  );
+
+  // The winner's locked funds, there is no record when nobody has bid
+  const hasBids = !nftInfoAccount.currentBidder.equals(
+    auctionStateAccount.seller
+  );
+  const [winnerRefundPDA] = PublicKey.findProgramAddressSync(
+    [
+      seed("PENDING_REFUND_SEEDS"),
+      Buffer.from(libraryName),
+      Buffer.from(nftName),
+      nftInfoAccount.currentBidder.toBuffer(),
+    ],
+    programId
+  );
+
+  const settleAuctionTx = await program.methods
+    .settleAuction(nftName, libraryName)
+    .accounts({
+      payer: wallet.payer.publicKey,
+      seller: auctionStateAccount.seller,
+      recipient: nftInfoAccount.currentBidder,
+      rentPayer: escrowAccount.payer,
+      winnerRefund: hasBids ? winnerRefundPDA : null,
+      protocolFeeAccount: libraryConfigAccount.protocolFeeAccount,
+      // Lamport-priced auction, no SPL payment accounts
+      paymentMint: null,
+      escrowPaymentAccount: null,
+      sellerPaymentAccount: null,
+      protocolFeePaymentAccount: null,
+      tokenProgram: TOKEN_PROGRAM_ID,
+    })
+    // The minter is the only verified creator, royalties go to their wallet
+    .remainingAccounts([
+      { pubkey: escrowAccount.payer, isWritable: true, isSigner: false },
+    ])
+    .signers([wallet.payer])
+    .instruction();
+
+  const getBLockHash = await provider.connection.getLatestBlockhash();
+
+  const tx = new web3.Transaction({
+    feePayer: wallet.publicKey,
+    blockhash: getBLockHash.blockhash,
+    lastValidBlockHeight: getBLockHash.lastValidBlockHeight,
+  }).add(settleAuctionTx);
+
+  console.warn({
+    settleAuction: await provider.sendAndConfirm(tx, [], {
+      skipPreflight: false,
+    }),
+  });
+})();
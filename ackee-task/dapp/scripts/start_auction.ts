@@ -5,18 +5,31 @@ import { provider } from "./anchor_provider";
 
 const { wallet } = provider;
 const program = workspace.dapp as Program<Dapp>;
-const endTime = Math.floor(Date.now() / 1000) + 86400;
 
-const [, , name] = process.argv; // Default to "Test" if not provided
+const [, , name, libraryName, extensionWindow, startTime] = process.argv; // Default to "Test" if not provided
 
-if (!name) {
-  console.error("Please provide name");
+if (!name || !libraryName) {
+  console.error("Please provide name and library name");
   process.exit(1);
 }
 
+// The auction runs for a day from its (scheduled) start
+const endTime =
+  (startTime ? Number(startTime) : Math.floor(Date.now() / 1000)) + 86400;
+
 (async () => {
   const startAutionTx = await program.methods
-    .startAuction(name, new BN(endTime))
+    .startAuction(
+      name,
+      libraryName,
+      // Unix timestamp of a scheduled drop, opens right away when omitted
+      startTime ? new BN(Number(startTime)) : null,
+      new BN(endTime),
+      new BN(Number(extensionWindow ?? 0)),
+      { english: {} },
+      // Open to every bidder, see `AuctionGate` for holder-only drops
+      null
+    )
     .accounts({
       seller: wallet.payer.publicKey,
     })
@@ -0,0 +1,64 @@
+import { web3, workspace, Program } from "@coral-xyz/anchor";
+import IDL from "../target/idl/dapp.json";
+import { PublicKey } from "@solana/web3.js";
+import { TOKEN_PROGRAM_ID } from "@solana/spl-token";
+
+import { Dapp } from "../target/types/dapp";
+import { provider } from "./anchor_provider";
+
+const [, , nftName, libraryName] = process.argv;
+
+if (!nftName || !libraryName) {
+  console.error("Please provide nft name and library name");
+  process.exit(1);
+}
+
+const seed = (name: string) =>
+  Buffer.from(JSON.parse(IDL.constants.find((c) => c.name === name).value));
+
+const programId = new PublicKey(IDL.address);
+
+const { wallet } = provider;
+const program = workspace.dapp as Program<Dapp>;
+
+(async () => {
+  const [escrowPDA] = PublicKey.findProgramAddressSync(
+    [
+      seed("BIDDER_ESCROW_SEEDS"),
+      Buffer.from(libraryName),
+      Buffer.from(nftName),
+    ],
+    programId
+  );
+  const escrowAccount = await program.account.escrowBidder.fetch(escrowPDA);
+
+  const withdrawRefundTx = await program.methods
+    .withdrawRefund(nftName, libraryName)
+    .accounts({
+      bidder: wallet.payer.publicKey,
+      rentPayer: escrowAccount.payer,
+      // Only set to close an unrevealed sealed bid commitment
+      sealedBid: null,
+      // Lamport-priced auction, no SPL payment accounts
+      paymentMint: null,
+      escrowPaymentAccount: null,
+      bidderPaymentAccount: null,
+      tokenProgram: TOKEN_PROGRAM_ID,
+    })
+    .signers([wallet.payer])
+    .instruction();
+
+  const getBLockHash = await provider.connection.getLatestBlockhash();
+
+  const tx = new web3.Transaction({
+    feePayer: wallet.publicKey,
+    blockhash: getBLockHash.blockhash,
+    lastValidBlockHeight: getBLockHash.lastValidBlockHeight,
+  }).add(withdrawRefundTx);
+
+  console.warn({
+    withdrawRefund: await provider.sendAndConfirm(tx, [], {
+      skipPreflight: false,
+    }),
+  });
+})();